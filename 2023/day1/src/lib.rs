@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use commons::{solution::Solution, AocError};
+
+pub struct Day1;
+
+// For part two. Doesn't work, but I learned a lot of string syntax.
+
+// Substitute the first and last instances of a written-out numbers, returning
+// another string with the substitutions.
+pub fn replace_written_numbers(input_string: String) -> String {
+    let mut output_str = input_string.clone();
+    let three_letter_replacements: HashMap<&str, &str> =
+        HashMap::from([("one", "1"), ("two", "2"), ("six", "6")]);
+    let four_letter_replacements: HashMap<&str, &str> =
+        HashMap::from([("zero", "0"), ("four", "4"), ("five", "5"), ("nine", "9")]);
+    let five_letter_replacements: HashMap<&str, &str> =
+        HashMap::from([("three", "3"), ("seven", "7"), ("eight", "8")]);
+
+    fn check_and_replace(
+        mutable_string: &mut String,
+        range: RangeInclusive<usize>,
+        replacement_map: &HashMap<&str, &str>,
+    ) -> bool {
+        match replacement_map.contains_key(&mutable_string[range.clone()]) {
+            true => {
+                mutable_string.replace_range(
+                    range.clone(),
+                    replacement_map.get(&mutable_string[range]).unwrap(),
+                );
+                true
+            }
+            false => false,
+        }
+    }
+
+    let mut found: bool = false;
+    if output_str.len() >= 5 {
+        // Slide a window over the string forwards, finding the first matched written digit
+        let end_idx = output_str.len() - 5;
+        for idx in 0..=end_idx {
+            // The first substitution that matches wins, so stop at it.
+            if check_and_replace(&mut output_str, idx..=idx + 2, &three_letter_replacements)
+                || check_and_replace(&mut output_str, idx..=idx + 3, &four_letter_replacements)
+                || check_and_replace(&mut output_str, idx..=idx + 4, &five_letter_replacements)
+            {
+                found = true;
+                break;
+            }
+        }
+    }
+    // Need to check at the 4th last character, for 3+4 letter subs
+    if !found && output_str.len() >= 4 {
+        let idx = output_str.len() - 4;
+        found = check_and_replace(&mut output_str, idx..=idx + 2, &three_letter_replacements)
+            || check_and_replace(&mut output_str, idx..=idx + 3, &four_letter_replacements);
+    }
+    if !found && output_str.len() >= 3 {
+        // check finally at the 3rd last character
+        let idx = output_str.len() - 3;
+        check_and_replace(&mut output_str, idx..=idx + 2, &three_letter_replacements);
+    }
+
+    let mut found_reverse: bool = false;
+    // Prologue to check the 3/4 letter substitutions from the end
+    if output_str.len() >= 3 {
+        let end_idx = output_str.len() - 3;
+        if check_and_replace(
+            &mut output_str,
+            end_idx..=end_idx + 2,
+            &three_letter_replacements,
+        ) {
+            found_reverse = true;
+        }
+    }
+    if !found_reverse && output_str.len() >= 4 {
+        let end_idx = output_str.len() - 4;
+        found_reverse = check_and_replace(
+            &mut output_str,
+            end_idx..=end_idx + 3,
+            &four_letter_replacements,
+        ) || check_and_replace(
+            &mut output_str,
+            end_idx..=end_idx + 2,
+            &three_letter_replacements,
+        );
+        if !found_reverse && output_str.len() >= 5 {
+            // Slide a window over the string backwards , finding the first matched written digit
+            for idx in (0..=output_str.len() - 5).rev() {
+                if check_and_replace(&mut output_str, idx..=idx + 4, &five_letter_replacements)
+                    || check_and_replace(&mut output_str, idx..=idx + 3, &four_letter_replacements)
+                    || check_and_replace(&mut output_str, idx..=idx + 2, &three_letter_replacements)
+                {
+                    break;
+                }
+            }
+        }
+    }
+    output_str
+}
+
+// Check if a given character is a digit.
+fn is_digit(a: char) -> bool {
+    matches!(a, '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9')
+}
+
+// For a given string, get the sum of the first and last digits appearing in it.
+fn get_digit_sum(s: &str) -> u32 {
+    let mut sum: u32 = 0;
+    for _char in s.chars() {
+        if is_digit(_char) {
+            sum += 10 * (_char.to_digit(10).unwrap());
+            break;
+        }
+    }
+
+    for _char in s.chars().rev() {
+        if is_digit(_char) {
+            sum += _char.to_digit(10).unwrap();
+            break;
+        }
+    }
+    sum
+}
+
+impl Solution for Day1 {
+    type Input = Vec<String>;
+
+    fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError> {
+        // replace_written_numbers slices the lines by byte, so they have to be ASCII.
+        raw_input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| match line.is_ascii() {
+                true => Ok(line.to_string()),
+                false => Err(AocError::parse(idx + 1, "Expected an ASCII line")),
+            })
+            .collect()
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Option<String> {
+        Some(
+            input
+                .iter()
+                .map(|line| get_digit_sum(line))
+                .sum::<u32>()
+                .to_string(),
+        )
+    }
+
+    // Same again, after replace_written_numbers. Which isn't right yet, see above.
+    fn part_two(&self, input: &Self::Input) -> Option<String> {
+        Some(
+            input
+                .iter()
+                .map(|line| get_digit_sum(&replace_written_numbers(line.to_string())))
+                .sum::<u32>()
+                .to_string(),
+        )
+    }
+}
@@ -0,0 +1,109 @@
+use commons::{solution::Solution, AocError};
+use regex::Regex;
+
+pub struct Day2;
+
+// A struct representing a draw, having a given number for all balls.
+struct Draw {
+    red: i32,
+    green: i32,
+    blue: i32,
+}
+
+// A struct representing a game, having an id and list of draws.
+pub struct Game {
+    id: i32,
+    draws: Vec<Draw>,
+}
+
+impl Game {
+    fn return_id_if_valid(&self) -> i32 {
+        // Constants provided by the problem statement.
+        const RED_PROVIDED: i32 = 12;
+        const BLUE_PROVIDED: i32 = 14;
+        const GREEN_PROVIDED: i32 = 13;
+        match self.draws.iter().all(|draw| {
+            //println!("Draw contined {} red, {} blue, and {} green", draw.red, draw.blue, draw.green);
+            draw.red <= RED_PROVIDED && draw.green <= GREEN_PROVIDED && draw.blue <= BLUE_PROVIDED
+        }) {
+            true => self.id,
+            false => 0,
+        }
+    }
+}
+
+// Parse a line of text and build a game out of it.
+fn parse_game(input_string: &str, line: usize) -> Result<Game, AocError> {
+    //println!("Matching on input string {}", input_string);
+    // get the game id with a regex
+    let re = Regex::new(r"Game (\d+).*").unwrap();
+    let Some(capture) = re.captures(input_string) else {
+        return Err(AocError::parse(line, "No game id could be captured!!"));
+    };
+    let game_id: i32 = capture[1]
+        .parse()
+        .map_err(|_| AocError::parse(line, format!("'{}' is not a game id", &capture[1])))?;
+
+    // split line by semicolons and match each one on a regex for the numbers and colors
+    let v: Vec<&str> = input_string.split(';').collect();
+    let sub_draw_re = Regex::new(r"(\d+)\s([[:alpha:]]+)").unwrap();
+
+    let re_draws = Regex::new(r"[:,\s]*(\d+\s[[:alpha:]]+)").unwrap();
+
+    let mut draw_vec: Vec<Draw> = Vec::new();
+    for split in v.iter() {
+        //println!("Raw split={}", split);
+        let mut num_blue = 0;
+        let mut num_green = 0;
+        let mut num_red = 0;
+        for (_, [sub_draw]) in re_draws.captures_iter(split).map(|c| c.extract()) {
+            //println!("full {}: digit={}",full, sub_draw);
+            for (_, [num_balls, color]) in sub_draw_re.captures_iter(sub_draw).map(|c| c.extract())
+            {
+                //println!("num_balls {}, color {}",num_balls, color);
+                let num_balls = num_balls.parse().map_err(|_| {
+                    AocError::parse(line, format!("'{num_balls}' is too many balls"))
+                })?;
+                match color {
+                    "red" => num_red = num_balls,
+                    "blue" => num_blue = num_balls,
+                    "green" => num_green = num_balls,
+                    _ => return Err(AocError::parse(line, format!("'{color}' is not a colour"))),
+                }
+            }
+        }
+        //println!("adding new draw: r{}, g{}, b{}", num_red, num_green, num_blue);
+        draw_vec.push(Draw {
+            red: num_red,
+            green: num_green,
+            blue: num_blue,
+        });
+    }
+
+    // Iterate over all the draws in the line and create draws for them.
+    Ok(Game {
+        id: game_id,
+        draws: draw_vec,
+    })
+}
+
+impl Solution for Day2 {
+    type Input = Vec<Game>;
+
+    fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError> {
+        raw_input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| parse_game(line, idx + 1))
+            .collect()
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Option<String> {
+        let game_sum: i32 = input.iter().map(|game| game.return_id_if_valid()).sum();
+        Some(game_sum.to_string())
+    }
+
+    fn part_two(&self, _input: &Self::Input) -> Option<String> {
+        None
+    }
+}
@@ -0,0 +1,20 @@
+use commons::{solution::Solution, AocError};
+
+pub struct Day3;
+
+// Not solved yet.
+impl Solution for Day3 {
+    type Input = ();
+
+    fn parse(&self, _raw_input: &str) -> Result<Self::Input, AocError> {
+        Ok(())
+    }
+
+    fn part_one(&self, _input: &Self::Input) -> Option<String> {
+        None
+    }
+
+    fn part_two(&self, _input: &Self::Input) -> Option<String> {
+        None
+    }
+}
@@ -1,3 +1,6 @@
+use aoc2023_day3::Day3;
+use commons::runner::run_day_from_env_args;
+
 fn main() {
-    println!("Hello, world!");
+    run_day_from_env_args(2023, 3, Day3);
 }
@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+
+use commons::{solution::Solution, AocError};
+use regex::Regex;
+
+pub struct Day4;
+
+// a struct representing a card, that has its string, number of matches, and copies.
+#[derive(Clone)]
+pub struct Card {
+    _card_str: String,
+    num_matches: u32,
+    num_copies: u32,
+}
+
+fn matches_for_card(input_string: &str, line: usize) -> Result<u32, AocError> {
+    // parse this card
+    let card_split: Vec<&str> = input_string.split('|').collect();
+    if card_split.len() != 2 {
+        return Err(AocError::parse(
+            line,
+            format!(
+                "Expected a card could be split into two, actually split into {}.",
+                card_split.len()
+            ),
+        ));
+    }
+
+    let mut winning_numbers: HashSet<i32> = HashSet::new();
+    let mut held_numbers: HashSet<i32> = HashSet::new();
+    let regex_spaced_digits = Regex::new(r"(\d+)\s").unwrap();
+    regex_spaced_digits
+        .captures_iter(card_split.first().unwrap())
+        .map(|c| c.extract())
+        .for_each(|(_matching_pattern, [n])| {
+            //println!("Matched full pattern={}, digit={}",_matching_pattern, n);
+            winning_numbers.insert(n.parse().unwrap());
+        });
+
+    let regex_second_part = Regex::new(r"\s+(\d+)").unwrap();
+    regex_second_part
+        .captures_iter(card_split.last().unwrap())
+        .map(|c| c.extract())
+        .map(|(_, [n])| {
+            held_numbers.insert(n.parse().unwrap());
+        })
+        .count();
+    Ok(winning_numbers
+        .intersection(&held_numbers)
+        .count()
+        .try_into()
+        .unwrap())
+}
+
+// Return the number of points a card is worth.
+fn points_for_card(card: &Card) -> i32 {
+    // The matches come from splitting the line on the pipe character, and then parsing both
+    // halves using a regex to match all numbers separated by spaces. The winning numbers go into
+    // a hash table (fast lookup) and then for all of the numbers we hold, we count the number of
+    // hits in the hash table.
+    // Number of points equals 2^(matches-1)
+    match card.num_matches {
+        0 => 0,
+        num_matches => 2_i32.checked_pow(num_matches - 1).unwrap(),
+    }
+}
+
+fn parse_cards(input: &str) -> Result<Vec<Card>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            Ok(Card {
+                _card_str: line.to_string(),
+                num_matches: matches_for_card(line, idx + 1)?,
+                num_copies: 1,
+            })
+        })
+        .collect()
+}
+
+fn count_total_cards(dat: &mut [Card]) -> u32 {
+    // algorithm:
+    // - go over the card index in increasing numerical order. For all cards,
+    // add their number of matches to subsequent cards.
+    let mut result = 0;
+    (0..dat.len()).for_each(|i| {
+        result += dat[i].num_copies;
+        for inc in 1..=dat[i].num_matches {
+            // add to all future cards "num-copies" of this card, saving us evaluating
+            // the current card multiple times.
+            dat[i + inc as usize].num_copies += dat[i].num_copies;
+        }
+    });
+    result
+}
+
+impl Solution for Day4 {
+    type Input = Vec<Card>;
+
+    fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError> {
+        parse_cards(raw_input)
+    }
+
+    // part 1
+    fn part_one(&self, input: &Self::Input) -> Option<String> {
+        let card_sum: i32 = input.iter().map(points_for_card).sum();
+        Some(card_sum.to_string())
+    }
+
+    // part 2 - count number of cards
+    fn part_two(&self, input: &Self::Input) -> Option<String> {
+        Some(count_total_cards(&mut input.clone()).to_string())
+    }
+}
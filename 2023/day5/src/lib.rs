@@ -0,0 +1,217 @@
+use std::cmp::{max, min};
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use commons::{solution::Solution, AocError};
+
+pub struct Day5;
+
+// The seed numbers from the first line, and the text of every map after it.
+pub struct Almanac {
+    seeds: Vec<i64>,
+    maps: Vec<String>,
+}
+
+// Return a tuple containing:
+// - whether this range was transformed
+// - a transformed range (optional)
+// - a remainder range (optional)
+fn overlap_and_exclusive(
+    input: &RangeInclusive<i64>,
+    source: &RangeInclusive<i64>,
+    offset: i64,
+) -> (Option<RangeInclusive<i64>>, Option<RangeInclusive<i64>>) {
+    //println!("Checking input range {:?} against source transform {:?}",input, source);
+    if source.contains(input.start()) && source.contains(input.end()) {
+        //println!("Input range {:?} completely contained in source {:?}", input, source);
+        // .... S1 -------------- Sn ....
+        // ........ I1............In ....
+        // .........O1............On .....
+        let overlap_start = max(source.start(), input.start());
+        let overlap_end = min(source.end(), input.end());
+        // result.push(RangeInclusive::new(
+        //     overlap_start+offset,
+        //     overlap_end+offset
+        // ));
+        (
+            Some(RangeInclusive::new(
+                overlap_start + offset,
+                overlap_end + offset,
+            )),
+            None,
+        )
+    } else if source.contains(input.start()) && !source.contains(input.end()) {
+        //println!("Input range {:?} partially overlaps source {:?} on the upper side", input, source);
+        // .... S1 -------------- Sn ....
+        // ................ I1................In ....
+        // Output: .........O1....On On+1.....In ....
+        let transformed_range = RangeInclusive::new(input.start() + offset, source.end() + offset);
+        let rem_range = RangeInclusive::new(source.end() + 1, *input.end());
+        (Some(transformed_range), Some(rem_range))
+    } else if !source.contains(input.start()) && source.contains(input.end()) {
+        //println!("Input range {:?} partially overlaps source {:?} on the lower side", input, source);
+        // ................. S1 -------------- Sn ....
+        // ........ I1................In ....
+        // Output: .I1..S1-1.S1 ......In ............
+        let rem_range = RangeInclusive::new(*input.start(), source.start() - 1);
+        let transformed_range = RangeInclusive::new(source.start() + offset, input.end() + offset);
+        (Some(transformed_range), Some(rem_range))
+    } else {
+        //println!("No overlap between the two ranges");
+        (None, None)
+    }
+}
+
+fn convert_seeds(maps: &[String], mut seed_ranges: Vec<RangeInclusive<i64>>) -> i64 {
+    // algorithm:
+    // - start from the list of seed ranges (input)
+    // - then, for every "map", build a HashSet containing the mappings of src->dest id
+    // - convert the seed ranges to the next representation, and finally output the minimum
+
+    //
+    // Algorithm:
+    // - for each "mapping" of seeds in the problem input set, create two other sets of ranges
+    //   like this: {[begin, end], offset}
+    //   e.g., {[98, 99], -48}, {[50, 97], 2}
+    let mut seed_map: HashMap<RangeInclusive<i64>, i64> = HashMap::new();
+    for map in maps {
+        //println!("Map: {:?}", map)
+        let mappings: Vec<&str> = map.split('\n').map(|s| s.trim()).collect();
+        //println!("Mappings: {:?}", mappings);
+        mappings.iter().for_each(|m| {
+            let range = m
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .iter()
+                .filter_map(|s| s.parse().ok())
+                .collect::<Vec<i64>>();
+            if range.len() == 3 {
+                // hack
+                let (dst, src, len) = match &range[..] {
+                    &[first, second, third, ..] => (first, second, third),
+                    _ => unreachable!(),
+                };
+                // Build the source range.
+                // -1 for using an inclusive range.
+                let source_range = RangeInclusive::new(src, src + len - 1);
+                // seed map contains an "offset"
+                let offset: i64 = dst - src;
+                // println!(
+                //     "Inserting source range w/ start {}, end {}, with an offset of {}",
+                //     source_range.start(), source_range.end(), offset,
+                // );
+                seed_map.insert(source_range, offset);
+            }
+        });
+
+        // - then, map the input seed ranges to a set of new ranges.
+        // - given input [90, 99], and an input of {[98, 99], -48}, {[50, 97], 2}
+        //   we would want the output as [92, 99], [50, 51]
+        //println!("Seed ranges before: {:?}", seed_ranges);
+
+        let mut next_step_ranges: Vec<RangeInclusive<i64>> = Vec::new();
+        let mut remainder_ranges: Vec<RangeInclusive<i64>> = Vec::new();
+        let mut ranges_to_consume = true;
+        while ranges_to_consume {
+            let mut transformed_ranges: Vec<RangeInclusive<i64>> = seed_ranges
+                .iter()
+                .flat_map(|input_range| {
+                    // return the range overlap between the src and the current range, and then
+                    // translate it to the destination
+                    let mut output_ranges: Vec<RangeInclusive<i64>> = Vec::new();
+                    let mut found = false;
+                    for (src, offset) in seed_map.iter() {
+                        let (transformed_range, remainder_range) =
+                            overlap_and_exclusive(input_range, src, *offset);
+                        if let Some(transformed_range) = transformed_range {
+                            //println!("Pushing transformed range: {:?}", transformed_range);
+                            output_ranges.push(transformed_range);
+                            found = true;
+                            if let Some(remainder_range) = remainder_range {
+                                //println!("Pushing remainder range: {:?}", remainder_range);
+                                remainder_ranges.push(remainder_range);
+                            }
+                            break;
+                        }
+                    }
+                    if !found {
+                        //println!("Pushing original range: {:?}", input_range);
+                        output_ranges.push(input_range.clone());
+                    }
+                    output_ranges.into_iter()
+                })
+                .collect();
+            //println!("Transformed ranges after: {:?}", transformed_ranges);
+            // seed_ranges has no more entries, because the iter consumed all of them
+            seed_ranges.clear();
+            next_step_ranges.append(&mut transformed_ranges);
+            if !remainder_ranges.is_empty() {
+                seed_ranges.append(&mut remainder_ranges);
+            } else {
+                ranges_to_consume = false;
+            }
+        }
+        // This step is done, replace all old ranges with new transformed ones
+        seed_ranges.clear();
+        seed_map.clear();
+        seed_ranges.append(&mut next_step_ranges);
+
+        // Now iterate over the seeds with the current map and transform them all
+        // seeds.iter_mut().for_each(|s_id| {
+        //     for (range, offset) in seed_map.iter() {
+        //         if range.contains(&s_id) {
+        //             *s_id = *s_id + offset;
+        //             break;
+        //         }
+        //     }
+        //     // let new_sid = seed_map.get(s_id).unwrap_or_else(|| s_id);
+        //     // *s_id = *new_sid;
+        // });
+        //println!("Seeds after = {:?}", seeds);
+    }
+    // *seeds.iter().min().unwrap()
+    *seed_ranges.iter().map(|range| range.start()).min().unwrap()
+}
+
+impl Solution for Day5 {
+    type Input = Almanac;
+
+    fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError> {
+        let input_vec: Vec<&str> = raw_input.trim().split(':').collect();
+        if input_vec.len() < 2 {
+            return Err(AocError::parse(1, "Expected a seeds: line"));
+        }
+        // The seeds are on the first line after "seeds:", the rest of that chunk is the
+        // name of the first map.
+        let seed_string = input_vec[1].split('\n').next().unwrap_or_default();
+        let seeds = seed_string
+            .split_whitespace()
+            .map(|c| {
+                c.parse()
+                    .map_err(|_| AocError::parse(1, format!("Bad seed number {}", c)))
+            })
+            .collect::<Result<Vec<i64>, AocError>>()?;
+        let maps = input_vec[2..].iter().map(|s| s.to_string()).collect();
+        Ok(Almanac { seeds, maps })
+    }
+
+    // PART 1 - individual seeds
+    fn part_one(&self, input: &Self::Input) -> Option<String> {
+        let seed_ranges = input
+            .seeds
+            .iter()
+            .map(|&s| RangeInclusive::new(s, s))
+            .collect();
+        Some(convert_seeds(&input.maps, seed_ranges).to_string())
+    }
+
+    // PART 2 - Seed ranges
+    fn part_two(&self, input: &Self::Input) -> Option<String> {
+        let seed_ranges = input
+            .seeds
+            .chunks_exact(2)
+            // -1 for using an inclusive range. 0 10 -> [0..9]
+            .map(|pair| RangeInclusive::new(pair[0], pair[0] + pair[1] - 1))
+            .collect();
+        Some(convert_seeds(&input.maps, seed_ranges).to_string())
+    }
+}
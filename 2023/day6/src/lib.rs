@@ -0,0 +1,111 @@
+use commons::{solution::Solution, AocError};
+use roots::{find_roots_quadratic, Roots};
+
+pub struct Day6;
+
+// Process file input into a vector of pairs, each one describing race distance
+// and time. e.g., for test input
+// Time:      7  15   30
+// Distance:  9  40  200
+// [(7,9), (15,40), (30,200)]
+fn read_input(input_str: &str) -> Result<Vec<(u64, u64)>, AocError> {
+    // split into lines, and then get two vectors representing distance + time
+    let lines: Vec<&str> = input_str.trim().split('\n').collect();
+    let parse_line_to_ivec = |s: &str| -> Vec<u64> {
+        s.split([':', ' '])
+            .filter_map(|num_candidate| num_candidate.parse().ok())
+            .collect()
+    };
+    let time_vec: Vec<u64> = parse_line_to_ivec(lines.first().unwrap());
+    let dist_vec: Vec<u64> = parse_line_to_ivec(lines.last().unwrap());
+    if time_vec.len() != dist_vec.len() {
+        return Err(AocError::parse(
+            lines.len(),
+            format!(
+                "Expected as many distances as times, got {} times and {} distances",
+                time_vec.len(),
+                dist_vec.len()
+            ),
+        ));
+    }
+    Ok(time_vec.into_iter().zip(dist_vec).collect())
+}
+
+// Concatenate all the race times and distances together into one big race, for part 2.
+fn join_races(races: &[(u64, u64)]) -> (u64, u64) {
+    let mut final_time = String::new();
+    let mut final_dist = String::new();
+    races.iter().for_each(|(t, d)| {
+        final_time.push_str(&t.to_string());
+        final_dist.push_str(&d.to_string());
+    });
+    (
+        final_time.parse().unwrap_or_default(),
+        final_dist.parse().unwrap_or_default(),
+    )
+}
+
+// calculate the product of all possible ways to beat the record in all races.
+// Algorithm: build a quadratic equation for each race, and solve it for the roots.
+// The number of wins for a race is the number of total solutions in the range of the
+// two roots.
+fn calc_wins(races: &[(u64, u64)]) -> u64 {
+    races
+        .iter()
+        .filter_map(|race| -> Option<u64> {
+            let t_lim = race.0;
+            let d_lim = race.1;
+            //println!("Solving the quadratic -x^2 + {}x - {} = 0", t_lim, d_lim);
+            // return roots of the quadratic equation
+            let eqn_offset: f64 = d_lim as f64;
+            let roots = find_roots_quadratic::<f64>(-1.0, t_lim as f64, -eqn_offset);
+
+            let beats_record = |choice: u64| ((t_lim - choice) * choice) > d_lim;
+
+            match roots {
+                Roots::No([]) => {
+                    //println!("No solutions, this race can't be won? Is that legit?");
+                    None
+                }
+                Roots::One([r1]) => {
+                    //println!("This race can only be won with one choice: {}", r1.ceil());
+                    if beats_record(r1.ceil() as u64) {
+                        return Some(1);
+                    }
+                    None
+                }
+                Roots::Two([r1, r2]) => {
+                    let lower_bound = if beats_record(r1.ceil() as u64) {
+                        r1.ceil() as u64
+                    } else {
+                        r1.ceil() as u64 + 1
+                    };
+                    let upper_bound = if beats_record(r2.floor() as u64) {
+                        r2.floor() as u64
+                    } else {
+                        r2.floor() as u64 - 1
+                    };
+                    //println!("This race can be won with any choice between {} and {}!", lower_bound, upper_bound);
+                    Some(upper_bound - lower_bound + 1)
+                }
+                _ => unreachable!(),
+            }
+        })
+        .product()
+}
+
+impl Solution for Day6 {
+    type Input = Vec<(u64, u64)>;
+
+    fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError> {
+        read_input(raw_input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Option<String> {
+        Some(calc_wins(input).to_string())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Option<String> {
+        Some(calc_wins(&[join_races(input)]).to_string())
+    }
+}
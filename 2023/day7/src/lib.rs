@@ -0,0 +1,167 @@
+// Goal: Rank all the hands from 1 to N, 1 being the worst, and N the best.
+// Find all the "5 of a kinds", then "4 of a kinds", "full houses", "3 of a kinds", "2 pair", "1 pair", "high card"
+// - Assign them ordinal numbers by sorting them based on the problem statement and popping them out sequentially
+// - only thing to implement is a custom ">" operator to compare 2 hands.
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use commons::{solution::Solution, AocError};
+
+pub struct Day7;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+enum HandType {
+    High,
+    Pair,
+    TwoPair,
+    ThreeKind,
+    FullHouse,
+    FourKind,
+    FiveKind,
+}
+
+// partial eq and eq can be done just with the native builtins, because
+// they do compare every element of the vector
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct CamelCardHand {
+    hand_type: HandType,
+    cards: Vec<u8>,
+    bid: i32,
+}
+
+// Hands are totally ordered, so this just defers to Ord below.
+impl PartialOrd for CamelCardHand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CamelCardHand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let type_order = self.hand_type.cmp(&other.hand_type);
+        if type_order == Ordering::Equal {
+            self.cards.cmp(&other.cards)
+        } else {
+            type_order
+        }
+    }
+}
+
+fn get_hand_type(hand: &str) -> HandType {
+    // five of a kind, easy to check for -> all elements in vector are the same.
+    let mut counts = HashMap::<char, u8>::new();
+    hand.chars().for_each(|c| {
+        counts.entry(c).and_modify(|count| *count += 1).or_insert(1);
+        //println!("Added {} to counts, new count {}", c, counts[&c]);
+    });
+
+    if counts.len() == 1 {
+        //println!("Is five of a kind!");
+        return HandType::FiveKind;
+    }
+
+    if counts.len() == 2 {
+        // full house or 4 of a kind
+        let four_kind = counts.values().any(|val| *val == 4);
+        if four_kind {
+            //println!("Is four of a kind!");
+            return HandType::FourKind;
+        } else {
+            //println!("Is full house!");
+            return HandType::FullHouse;
+        }
+    } else if counts.len() == 3 {
+        // trips or 2 pair
+        let trips = counts.values().any(|val| *val == 3);
+        if trips {
+            //println!("Is three of a kind!");
+            return HandType::ThreeKind;
+        } else {
+            //println!("Is two pair!");
+            return HandType::TwoPair;
+        }
+    } else if counts.len() == 4 {
+        //println!("Is one pair!");
+        return HandType::Pair;
+    }
+    // if nothing else, this is a high card hand
+    assert_eq!(counts.len(), 5);
+    HandType::High
+}
+
+fn build_hand(raw_string: &str, line: usize) -> Result<CamelCardHand, AocError> {
+    // there has to be a better way to optimize this instead of creating a
+    // new hashmap every time
+    let to_ordinal = HashMap::from([
+        ('2', 2),
+        ('3', 3),
+        ('4', 4),
+        ('5', 5),
+        ('6', 6),
+        ('7', 7),
+        ('8', 8),
+        ('9', 9),
+        ('T', 10),
+        ('J', 11),
+        ('Q', 12),
+        ('K', 13),
+        ('A', 14),
+    ]);
+    let cards_and_bid: Vec<&str> = raw_string.split_whitespace().collect();
+    let (hand, bid) = match &cards_and_bid[..] {
+        [hand, bid] => (*hand, *bid),
+        _ => return Err(AocError::parse(line, "Expected a hand and a bid")),
+    };
+    if hand.chars().count() != 5 {
+        return Err(AocError::parse(
+            line,
+            format!("Expected five cards, got {}", hand),
+        ));
+    }
+    let cards = hand
+        .chars()
+        .map(|c| {
+            to_ordinal
+                .get(&c)
+                .copied()
+                .ok_or_else(|| AocError::parse(line, format!("Unknown card {}", c)))
+        })
+        .collect::<Result<Vec<u8>, AocError>>()?;
+    Ok(CamelCardHand {
+        cards,
+        bid: bid
+            .parse()
+            .map_err(|_| AocError::parse(line, format!("Bad bid {}", bid)))?,
+        hand_type: get_hand_type(hand),
+    })
+}
+
+impl Solution for Day7 {
+    type Input = Vec<CamelCardHand>;
+
+    fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError> {
+        raw_input
+            .split('\n')
+            .enumerate()
+            .filter(|(_, s)| !s.trim().is_empty())
+            .map(|(idx, s)| build_hand(s, idx + 1))
+            .collect()
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Option<String> {
+        let mut hands = input.clone();
+        hands.sort();
+
+        let pts: i32 = hands
+            .iter()
+            .enumerate()
+            .map(|h| (h.0 + 1) as i32 * h.1.bid)
+            .sum();
+        Some(pts.to_string())
+    }
+
+    // Part 2 makes J a wildcard, and the hand types don't account for that yet.
+    fn part_two(&self, _input: &Self::Input) -> Option<String> {
+        None
+    }
+}
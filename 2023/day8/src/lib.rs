@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use commons::{solution::Solution, AocError};
+use regex::Regex;
+
+pub struct Day8;
+
+#[derive(Default)]
+pub struct Network {
+    directions: Vec<char>,
+    nodes: HashMap<String, (String, String)>,
+    start_list: Vec<String>,
+}
+
+impl Network {
+    // Use a builder class to create the map
+    pub fn builder(input: &str) -> NetworkBuilder<'_> {
+        NetworkBuilder::new(input)
+    }
+
+    // Functions to get the next node in L and R directions
+    // Can we do this without clone? Sounds terrible for runtime.
+    pub fn get_left_node(&self, cur_node: &str) -> String {
+        self.nodes[cur_node].0.clone()
+    }
+
+    pub fn get_right_node(&self, cur_node: &str) -> String {
+        self.nodes[cur_node].1.clone()
+    }
+}
+
+pub struct NetworkBuilder<'a> {
+    network_input: &'a str,
+}
+
+impl<'a> NetworkBuilder<'a> {
+    pub fn new(network_input: &'a str) -> NetworkBuilder<'a> {
+        NetworkBuilder { network_input }
+    }
+
+    pub fn build(self) -> Result<Network, AocError> {
+        let mut net: Network = Network::default();
+        let lines: Vec<&str> = self.network_input.split("\n").collect();
+
+        let mut starting_node_strings: Vec<String> = Vec::new();
+        // the first line contains all the directions (e.g., LRLR) so split that into
+        // the Network's "directions" field
+        net.directions = lines.first().unwrap().trim().chars().collect();
+        if net.directions.is_empty() {
+            return Err(AocError::parse(1, "Expected a line of directions"));
+        }
+        if let Some(bad) = net.directions.iter().find(|c| **c != 'L' && **c != 'R') {
+            return Err(AocError::parse(1, format!("Unknown direction {}", bad)));
+        }
+
+        // All lines contain nodes and their adjacencies, so match each one against
+        // this pattern, for e.g.,
+        //  AAA = (BBB, CCC)
+        //  (Node) = (Next-Node-L, Next-Node-R)
+        //  (\W+) = (\W+, \W+)
+        let mut map: HashMap<String, (String, String)> = HashMap::new();
+        let re =
+            Regex::new(r"([[[:alpha:]]\d]+)\s=\s\x28([[[:alpha:]]\d]+),\s([[[:alpha:]]\d]+)\x29")
+                .unwrap();
+        lines.iter().for_each(|line| {
+            for (_, [node, l_str, r_str]) in re.captures_iter(line).map(|c| c.extract()) {
+                // We know all the keys are unique (tested and implied in the problem statement)
+                map.insert(node.to_string(), (l_str.to_string(), r_str.to_string()));
+
+                // Put starting nodes on the list
+                if node.ends_with('A') {
+                    starting_node_strings.push(node.to_string());
+                }
+            }
+        });
+        // Every node we can step to has to be in the map, or walking the network would panic
+        if let Some((node, _)) = map
+            .iter()
+            .find(|(_, (l, r))| !map.contains_key(l) || !map.contains_key(r))
+        {
+            return Err(AocError::validation(format!(
+                "Node {} leads to a node that isn't in the network",
+                node
+            )));
+        }
+        net.start_list = starting_node_strings;
+        net.nodes = map;
+        Ok(net)
+    }
+}
+
+fn get_next_node(direction_idx: usize, cur_node: &str, network: &Network) -> String {
+    match network.directions[direction_idx] {
+        'L' => network.get_left_node(cur_node),
+        'R' => network.get_right_node(cur_node),
+        _ => unreachable!(),
+    }
+}
+
+fn check_termination(nodes: &[String]) -> bool {
+    nodes.iter().all(|node| node.ends_with('Z'))
+}
+
+impl Solution for Day8 {
+    type Input = Network;
+
+    fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError> {
+        Network::builder(raw_input).build()
+    }
+
+    // Part 1 - start from one node and traverse to the end 'ZZZ'
+    fn part_one(&self, network: &Self::Input) -> Option<String> {
+        if !network.nodes.contains_key("AAA") {
+            return None;
+        }
+        let mut cur_node = "AAA".to_string();
+        let mut steps: u64 = 0;
+        let mut direction_idx: usize = 0;
+        while cur_node != "ZZZ" {
+            cur_node = get_next_node(direction_idx, &cur_node, network);
+            steps += 1;
+            direction_idx = (direction_idx + 1) % network.directions.len();
+        }
+        Some(steps.to_string())
+    }
+
+    // Part 2 - Start from all nodes ending in A, and go to all those ending in Z.
+    // Algorithm:
+    // - create two sets: one with the input nodes, one with the output
+    // - map each input node to an output
+    // - check for termination
+    // - swap the two set references, so the output becomes the input of the next step
+    fn part_two(&self, network: &Self::Input) -> Option<String> {
+        if network.start_list.is_empty() {
+            return None;
+        }
+        let mut steps: u64 = 0;
+        let mut direction_idx: usize = 0;
+        // could optimize this to not clone the start list and strings all the time
+        let mut starting_nodes: Vec<String> = network.start_list.clone();
+        let mut output_nodes: Vec<String> = Vec::new();
+        let mut ptr_1: &mut Vec<String> = &mut starting_nodes;
+        let mut ptr_2: &mut Vec<String> = &mut output_nodes;
+        loop {
+            if check_termination(ptr_1) {
+                break;
+            }
+            ptr_1.iter().for_each(|node| {
+                ptr_2.push(get_next_node(direction_idx, node, network));
+            });
+            steps += 1;
+
+            // swap the contents of the two references and reset direction_idx for
+            // the next iteration
+            std::mem::swap(&mut ptr_1, &mut ptr_2);
+            ptr_2.clear();
+            direction_idx += 1;
+            if direction_idx >= network.directions.len() {
+                direction_idx = 0;
+            }
+        }
+        Some(steps.to_string())
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::Path;
+
+use crate::input_store::{InputKind, InputStore};
+use crate::io_utilities::read_file_to_string;
+use crate::solution::Part;
+use crate::AocError;
+
+// The accepted answers for puzzles we've already solved, so that refactors can be checked against
+// them. The answers file lives at the root of the input store next to the inputs it describes,
+// with one answer per line:
+//
+//     # <year> <day> <part> <kind> <answer>
+//     2024 6 1 example1 41
+//
+// Blank lines and lines starting with '#' are ignored.
+
+const ANSWERS_FILE: &str = "answers.txt";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    // No accepted answer is recorded, so there's nothing to compare against.
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected } => write!(f, "FAIL, expected {expected}"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+fn part_number(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+        Part::Both => unreachable!("answers are recorded for one part at a time"),
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AnswerBook {
+    // Keyed by (year, day, part number, kind), so iteration runs in calendar order.
+    answers: BTreeMap<(u32, u32, u8, InputKind), String>,
+}
+
+impl AnswerBook {
+    pub fn parse(contents: &str) -> Result<Self, AocError> {
+        let mut book = AnswerBook::default();
+        for (idx, line) in contents.lines().enumerate() {
+            let line_number = idx + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [year, day, part, kind, answer] = fields[..] else {
+                return Err(AocError::parse(
+                    line_number,
+                    format!(
+                        "expected '<year> <day> <part> <kind> <answer>', found {} fields",
+                        fields.len()
+                    ),
+                ));
+            };
+            let number = |field: &str, what: &str| {
+                field.parse::<u32>().map_err(|_| {
+                    AocError::parse(line_number, format!("'{field}' is not a valid {what}"))
+                })
+            };
+            let part = match part {
+                "1" => Part::One,
+                "2" => Part::Two,
+                _ => {
+                    return Err(AocError::parse(
+                        line_number,
+                        format!("'{part}' is not a part, expected 1 or 2"),
+                    ))
+                }
+            };
+            let kind = kind
+                .parse::<InputKind>()
+                .map_err(|err| AocError::parse(line_number, err.to_string()))?;
+            let (year, day) = (number(year, "year")?, number(day, "day")?);
+            if book.get(year, day, part, kind).is_some() {
+                return Err(AocError::parse(
+                    line_number,
+                    format!("{year} day {day} part {part} {kind} already has an answer"),
+                ));
+            }
+            book.insert(year, day, part, kind, answer);
+        }
+        Ok(book)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        AnswerBook::parse(&read_file_to_string(&path.to_string_lossy())?)
+    }
+
+    // Load the answers kept at the root of the input store. A store without an answers file just
+    // has no known answers.
+    pub fn load_from_store(store: &InputStore) -> Result<Self, AocError> {
+        let path = store.root().join(ANSWERS_FILE);
+        if path.is_file() {
+            AnswerBook::load(&path)
+        } else {
+            Ok(AnswerBook::default())
+        }
+    }
+
+    pub fn insert(&mut self, year: u32, day: u32, part: Part, kind: InputKind, answer: &str) {
+        self.answers
+            .insert((year, day, part_number(part), kind), answer.to_string());
+    }
+
+    pub fn get(&self, year: u32, day: u32, part: Part, kind: InputKind) -> Option<&str> {
+        self.answers
+            .get(&(year, day, part_number(part), kind))
+            .map(String::as_str)
+    }
+
+    // Compare a computed answer against the recorded one. A part that isn't solved yet fails if an
+    // answer has been recorded for it, since that means it used to be solved.
+    pub fn check(
+        &self,
+        year: u32,
+        day: u32,
+        part: Part,
+        kind: InputKind,
+        computed: Option<&str>,
+    ) -> Verdict {
+        match (self.get(year, day, part, kind), computed) {
+            (None, _) => Verdict::Unknown,
+            (Some(expected), Some(computed)) if expected == computed => Verdict::Pass,
+            (Some(expected), _) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+
+    // Every (year, day, kind) that has at least one recorded answer, in calendar order.
+    pub fn recorded_inputs(&self) -> Vec<(u32, u32, InputKind)> {
+        let mut inputs: Vec<(u32, u32, InputKind)> = self
+            .answers
+            .keys()
+            .map(|(year, day, _, kind)| (*year, *day, *kind))
+            .collect();
+        inputs.sort();
+        inputs.dedup();
+        inputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = "# year day part kind answer\n\
+                           2024 6 1 example1 41\n\
+                           2024 6 2 example1 6\n\
+                           \n\
+                           2024 1 1 full 1234\n\
+                           2024 1 1 example1 11\n";
+
+    #[test]
+    fn test_parse_and_get() {
+        let book = AnswerBook::parse(ANSWERS).unwrap();
+        assert_eq!(
+            book.get(2024, 6, Part::One, InputKind::Example(1)),
+            Some("41")
+        );
+        assert_eq!(
+            book.get(2024, 6, Part::Two, InputKind::Example(1)),
+            Some("6")
+        );
+        assert_eq!(book.get(2024, 1, Part::One, InputKind::Full), Some("1234"));
+        assert_eq!(book.get(2024, 6, Part::One, InputKind::Full), None);
+        assert_eq!(
+            book.recorded_inputs(),
+            vec![
+                (2024, 1, InputKind::Example(1)),
+                (2024, 1, InputKind::Full),
+                (2024, 6, InputKind::Example(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for (contents, line) in [
+            ("2024 6 1 example1\n", 1),
+            ("\n2024 6 3 example1 41\n", 2),
+            ("2024 six 1 example1 41\n", 1),
+            ("2024 6 1 sample 41\n", 1),
+            ("2024 6 1 full 41\n2024 6 1 full 42\n", 2),
+        ] {
+            match AnswerBook::parse(contents) {
+                Err(AocError::Parse { line: l, .. }) => assert_eq!(l, line, "{contents:?}"),
+                other => panic!("Expected a parse error for {contents:?}, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let book = AnswerBook::parse(ANSWERS).unwrap();
+        let example = InputKind::Example(1);
+        assert_eq!(
+            book.check(2024, 6, Part::One, example, Some("41")),
+            Verdict::Pass
+        );
+        assert_eq!(
+            book.check(2024, 6, Part::One, example, Some("40")),
+            Verdict::Fail {
+                expected: "41".to_string()
+            }
+        );
+        assert_eq!(
+            book.check(2024, 6, Part::Two, example, None),
+            Verdict::Fail {
+                expected: "6".to_string()
+            }
+        );
+        assert_eq!(
+            book.check(2024, 2, Part::One, example, Some("2")),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn test_repository_answers_parse() {
+        let store = InputStore::from_env().unwrap();
+        let book = AnswerBook::load_from_store(&store).unwrap();
+        assert!(!book.recorded_inputs().is_empty());
+    }
+}
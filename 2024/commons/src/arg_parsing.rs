@@ -0,0 +1,482 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use crate::AocError;
+
+// A small declarative command line parser. Binaries describe their flags and positional
+// arguments up front, and get parsing, defaults, validation and `--help` output from the
+// description:
+//
+//     let parser = ArgParser::new("day5", "Sums the middle pages of each update.")
+//         .flag(Flag::value("part", "PART", "Which part to run").default_value("both"))
+//         .flag(Flag::switch("verbose", "Print timings"))
+//         .positional(Positional::new("files", "Input files").count(1, None));
+//     let args = parser.parse_env_or_exit();
+//
+// Flags are written `--name value` or `--name=value`. A flag with an implicit value may also be
+// given bare (`--example`), in which case only the `=` form attaches an explicit value, so that a
+// following positional argument isn't swallowed by it. A flag with a value can only be given once.
+pub struct ArgParser {
+    program: String,
+    about: String,
+    flags: Vec<Flag>,
+    positional: Positional,
+}
+
+pub struct Flag {
+    name: &'static str,
+    value_name: Option<&'static str>, // None for on/off switches
+    help: &'static str,
+    default: Option<&'static str>,
+    implicit: Option<&'static str>,
+    required: bool,
+}
+
+pub struct Positional {
+    name: &'static str,
+    help: &'static str,
+    min: usize,
+    max: Option<usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct ParsedArgs {
+    values: HashMap<&'static str, String>,
+    switches: HashSet<&'static str>,
+    positionals: Vec<String>,
+    help_requested: bool,
+}
+
+impl Flag {
+    // A flag that is either present or not, e.g. `--verbose`.
+    pub fn switch(name: &'static str, help: &'static str) -> Self {
+        Flag {
+            name,
+            value_name: None,
+            help,
+            default: None,
+            implicit: None,
+            required: false,
+        }
+    }
+
+    // A flag that takes a value, e.g. `--part 2`.
+    pub fn value(name: &'static str, value_name: &'static str, help: &'static str) -> Self {
+        Flag {
+            value_name: Some(value_name),
+            ..Flag::switch(name, help)
+        }
+    }
+
+    // The value used when the flag isn't given at all.
+    pub fn default_value(mut self, default: &'static str) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    // The value used when the flag is given without one, e.g. `--example` meaning `--example=1`.
+    pub fn implicit_value(mut self, implicit: &'static str) -> Self {
+        self.implicit = Some(implicit);
+        self
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    fn usage(&self) -> String {
+        match (self.value_name, self.implicit) {
+            (None, _) => format!("--{}", self.name),
+            (Some(value_name), None) => format!("--{} <{}>", self.name, value_name),
+            (Some(value_name), Some(_)) => format!("--{}[=<{}>]", self.name, value_name),
+        }
+    }
+}
+
+impl Positional {
+    // By default a binary takes any number of positional arguments, including none.
+    pub fn new(name: &'static str, help: &'static str) -> Self {
+        Positional {
+            name,
+            help,
+            min: 0,
+            max: None,
+        }
+    }
+
+    // Bound the number of positional arguments. `max` of None means unbounded.
+    pub fn count(mut self, min: usize, max: Option<usize>) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    fn usage(&self) -> String {
+        match (self.min, self.max) {
+            (_, Some(0)) => String::new(),
+            (0, Some(1)) => format!("[{}]", self.name),
+            (1, Some(1)) => format!("<{}>", self.name),
+            (0, _) => format!("[{}...]", self.name),
+            _ => format!("<{}>...", self.name),
+        }
+    }
+}
+
+impl ArgParser {
+    pub fn new(program: &str, about: &str) -> Self {
+        ArgParser {
+            program: program.to_string(),
+            about: about.to_string(),
+            flags: Vec::new(),
+            positional: Positional::new("args", "").count(0, Some(0)),
+        }
+    }
+
+    pub fn flag(mut self, flag: Flag) -> Self {
+        assert!(
+            self.find_flag(flag.name).is_none() && flag.name != "help",
+            "Flag --{} is declared twice!",
+            flag.name
+        );
+        self.flags.push(flag);
+        self
+    }
+
+    pub fn positional(mut self, positional: Positional) -> Self {
+        self.positional = positional;
+        self
+    }
+
+    fn find_flag(&self, name: &str) -> Option<&Flag> {
+        self.flags.iter().find(|flag| flag.name == name)
+    }
+
+    pub fn usage(&self) -> String {
+        let mut usage = format!("Usage: {}", self.program);
+        for flag in self.flags.iter() {
+            if flag.required {
+                usage.push_str(&format!(" {}", flag.usage()));
+            } else {
+                usage.push_str(&format!(" [{}]", flag.usage()));
+            }
+        }
+        let positional_usage = self.positional.usage();
+        if !positional_usage.is_empty() {
+            usage.push_str(&format!(" {}", positional_usage));
+        }
+        usage
+    }
+
+    pub fn help(&self) -> String {
+        let mut help = format!("{}\n\n{}\n\nOptions:\n", self.about, self.usage());
+        let mut rows: Vec<(String, String)> = self
+            .flags
+            .iter()
+            .map(|flag| {
+                let mut description = flag.help.to_string();
+                if let Some(default) = flag.default {
+                    description.push_str(&format!(" [default: {default}]"));
+                }
+                if flag.required {
+                    description.push_str(" (required)");
+                }
+                (flag.usage(), description)
+            })
+            .collect();
+        rows.push(("--help".to_string(), "Print this help".to_string()));
+        if self.positional.max != Some(0) {
+            rows.insert(
+                0,
+                (self.positional.usage(), self.positional.help.to_string()),
+            );
+        }
+        let width = rows.iter().map(|(left, _)| left.len()).max().unwrap_or(0);
+        rows.iter().for_each(|(left, right)| {
+            help.push_str(&format!("  {:width$}  {}\n", left, right, width = width));
+        });
+        help
+    }
+
+    // Parse the arguments (without the program name). Asking for `--help` skips validation, so
+    // callers should check `help_requested()` before reading any values.
+    pub fn parse(&self, args: &[String]) -> Result<ParsedArgs, AocError> {
+        let mut parsed = ParsedArgs::default();
+        let mut iter = args.iter();
+        let mut flags_done = false;
+        while let Some(arg) = iter.next() {
+            if flags_done || !arg.starts_with("--") {
+                parsed.positionals.push(arg.clone());
+                continue;
+            }
+            if arg == "--" {
+                // Everything after a bare `--` is positional, even if it looks like a flag.
+                flags_done = true;
+                continue;
+            }
+            if arg == "--help" {
+                parsed.help_requested = true;
+                return Ok(parsed);
+            }
+
+            let (name, inline_value) = match arg[2..].split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (&arg[2..], None),
+            };
+            let flag = self
+                .find_flag(name)
+                .ok_or_else(|| AocError::argument(format!("Unknown flag --{name}")))?;
+            let value = match (flag.value_name, inline_value) {
+                (None, None) => {
+                    parsed.switches.insert(flag.name);
+                    continue;
+                }
+                (None, Some(_)) => {
+                    return Err(AocError::argument(format!(
+                        "Flag --{name} doesn't take a value"
+                    )))
+                }
+                (Some(_), Some(value)) => value,
+                (Some(_), None) => {
+                    // A flag in the value's place (`--input --verbose`) means the value was left
+                    // out. Values that really do start with `--` can use the `=` form.
+                    match flag.implicit {
+                        Some(implicit) => implicit.to_string(),
+                        None => iter
+                            .next()
+                            .filter(|value| !value.starts_with("--"))
+                            .cloned()
+                            .ok_or_else(|| {
+                                AocError::argument(format!("Missing value for --{name}"))
+                            })?,
+                    }
+                }
+            };
+            if parsed.values.insert(flag.name, value).is_some() {
+                return Err(AocError::argument(format!(
+                    "Flag --{name} was given more than once"
+                )));
+            }
+        }
+
+        for flag in self.flags.iter() {
+            if parsed.values.contains_key(flag.name) {
+                continue;
+            }
+            if flag.required {
+                return Err(AocError::argument(format!(
+                    "Missing required flag --{}",
+                    flag.name
+                )));
+            }
+            if let Some(default) = flag.default {
+                parsed.values.insert(flag.name, default.to_string());
+            }
+        }
+
+        let count = parsed.positionals.len();
+        let too_many = self.positional.max.is_some_and(|max| count > max);
+        if count < self.positional.min || too_many {
+            return Err(AocError::argument(format!(
+                "Got {} positional argument(s), expected {}",
+                count,
+                match self.positional.max {
+                    Some(max) if max == self.positional.min => max.to_string(),
+                    Some(max) => format!("{} to {}", self.positional.min, max),
+                    None => format!("at least {}", self.positional.min),
+                }
+            )));
+        }
+        Ok(parsed)
+    }
+
+    // Parse the process arguments. Prints the help and exits successfully on `--help`, and
+    // prints the error and usage and exits with code 1 on bad arguments.
+    pub fn parse_env_or_exit(&self) -> ParsedArgs {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        match self.parse(&args) {
+            Ok(parsed) if parsed.help_requested() => {
+                print!("{}", self.help());
+                std::process::exit(0);
+            }
+            Ok(parsed) => parsed,
+            Err(err) => {
+                println!("{err}");
+                println!("{}", self.usage());
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+impl ParsedArgs {
+    // The flag's value, falling back to its default. None if neither was given.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(|value| value.as_str())
+    }
+
+    // The flag's value converted with FromStr, reporting the flag name if conversion fails.
+    pub fn parsed_value<T: FromStr>(&self, name: &str) -> Result<Option<T>, AocError> {
+        self.value(name)
+            .map(|value| {
+                value.parse::<T>().map_err(|_| {
+                    AocError::argument(format!("Invalid value '{value}' for --{name}"))
+                })
+            })
+            .transpose()
+    }
+
+    pub fn is_set(&self, name: &str) -> bool {
+        self.switches.contains(name)
+    }
+
+    pub fn positionals(&self) -> &[String] {
+        &self.positionals
+    }
+
+    pub fn help_requested(&self) -> bool {
+        self.help_requested
+    }
+}
+
+// The flags shared by every puzzle binary: which part to run, where the input comes from, and
+// how chatty to be. Binaries add their own flags on top of these.
+pub fn puzzle_arg_parser(program: &str, about: &str) -> ArgParser {
+    ArgParser::new(program, about)
+        .flag(Flag::value("part", "PART", "Which part to run: 1, 2 or both").default_value("both"))
+        .flag(Flag::value(
+            "input",
+            "FILE",
+            "Puzzle input file [default: the full input from the input store]",
+        ))
+        .flag(
+            Flag::value(
+                "example",
+                "N",
+                "Run on the puzzle's example input N instead of a file",
+            )
+            .implicit_value("1"),
+        )
+        .flag(Flag::value(
+            "input-root",
+            "DIR",
+            "Root of the input store [default: $AOC_INPUT_ROOT, or this repository]",
+        ))
+        .flag(Flag::switch("verbose", "Print input paths and timings"))
+        .positional(Positional::new(
+            "inputs",
+            "Puzzle input files, joined as blank-line separated sections",
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    fn test_parser() -> ArgParser {
+        ArgParser::new("test", "A test binary.")
+            .flag(Flag::value("day", "DAY", "Puzzle day").required())
+            .flag(Flag::value("part", "PART", "Which part").default_value("both"))
+            .flag(Flag::value("example", "N", "Example number").implicit_value("1"))
+            .flag(Flag::switch("verbose", "Be chatty"))
+            .positional(Positional::new("files", "Input files").count(0, Some(2)))
+    }
+
+    #[test]
+    fn test_parse_flags_and_defaults() {
+        let parsed = test_parser()
+            .parse(&to_args(&["--day", "5", "rules", "--verbose", "updates"]))
+            .unwrap();
+        assert_eq!(parsed.value("day"), Some("5"));
+        assert_eq!(parsed.parsed_value::<u32>("day").unwrap(), Some(5));
+        assert_eq!(parsed.value("part"), Some("both"));
+        assert_eq!(parsed.value("example"), None);
+        assert!(parsed.is_set("verbose"));
+        assert_eq!(parsed.positionals(), &to_args(&["rules", "updates"]));
+        assert!(!parsed.help_requested());
+    }
+
+    #[test]
+    fn test_parse_inline_and_implicit_values() {
+        let parsed = test_parser()
+            .parse(&to_args(&["--day=6", "--part=2", "--example", "file"]))
+            .unwrap();
+        assert_eq!(parsed.value("day"), Some("6"));
+        assert_eq!(parsed.value("part"), Some("2"));
+        // A bare implicit flag doesn't consume the following positional argument.
+        assert_eq!(parsed.value("example"), Some("1"));
+        assert_eq!(parsed.positionals(), &to_args(&["file"]));
+        let parsed = test_parser()
+            .parse(&to_args(&["--day", "6", "--example=2"]))
+            .unwrap();
+        assert_eq!(parsed.value("example"), Some("2"));
+        assert!(!parsed.is_set("verbose"));
+    }
+
+    #[test]
+    fn test_double_dash_ends_flags() {
+        let parsed = test_parser()
+            .parse(&to_args(&["--day", "1", "--", "--not-a-flag"]))
+            .unwrap();
+        assert_eq!(parsed.positionals(), &to_args(&["--not-a-flag"]));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parser = test_parser();
+        // Missing required flag, unknown flag, missing values, switch given a value, a value given
+        // twice, and too many positional arguments.
+        for args in [
+            vec!["file"],
+            vec!["--day", "1", "--bogus"],
+            vec!["--day"],
+            vec!["--day", "--verbose"],
+            vec!["--day", "1", "--part", "--", "file"],
+            vec!["--day", "1", "--verbose=yes"],
+            vec!["--day", "1", "--day", "2"],
+            vec!["--day", "1", "--example", "--example=2"],
+            vec!["--day", "1", "a", "b", "c"],
+        ] {
+            assert!(
+                matches!(
+                    parser.parse(&to_args(&args)),
+                    Err(AocError::Argument { .. })
+                ),
+                "Expected an error for {:?}",
+                args
+            );
+        }
+        let parsed = parser.parse(&to_args(&["--day", "one"])).unwrap();
+        assert!(parsed.parsed_value::<u32>("day").is_err());
+        // The `=` form can still give a value that looks like a flag.
+        let parsed = parser.parse(&to_args(&["--day=--1"])).unwrap();
+        assert_eq!(parsed.value("day"), Some("--1"));
+    }
+
+    #[test]
+    fn test_help() {
+        let parser = test_parser();
+        let parsed = parser.parse(&to_args(&["--help"])).unwrap();
+        assert!(parsed.help_requested());
+        let help = parser.help();
+        assert!(help.starts_with("A test binary."));
+        assert!(help.contains(
+            "Usage: test --day <DAY> [--part <PART>] [--example[=<N>]] [--verbose] [files...]"
+        ));
+        assert!(help.contains("[default: both]"));
+        assert!(help.contains("(required)"));
+        assert!(help.contains("--help"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duplicate_flags_are_rejected() {
+        let _ = ArgParser::new("test", "")
+            .flag(Flag::switch("verbose", ""))
+            .flag(Flag::switch("verbose", ""));
+    }
+}
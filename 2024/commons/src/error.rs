@@ -0,0 +1,117 @@
+use std::fmt::Display;
+
+// The error type shared by commons and the solutions. Every variant carries enough context to
+// report the failure without a backtrace: the file involved, the offending argument, or the line
+// of input that couldn't be understood.
+#[derive(Debug)]
+pub enum AocError {
+    // A file couldn't be read.
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    // The command line was missing something or had something unexpected.
+    Argument {
+        message: String,
+    },
+    // A line of input couldn't be parsed. Line and column numbers start at 1, and the column is
+    // only known when the error came from a specific spot in the line.
+    Parse {
+        line: usize,
+        column: Option<usize>,
+        message: String,
+    },
+    // The input parsed, but doesn't describe a valid puzzle (e.g., a lab with no guard).
+    Validation {
+        message: String,
+    },
+}
+
+impl AocError {
+    pub fn argument(message: impl Into<String>) -> Self {
+        AocError::Argument {
+            message: message.into(),
+        }
+    }
+
+    pub fn parse(line: usize, message: impl Into<String>) -> Self {
+        AocError::Parse {
+            line,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn parse_at(line: usize, column: usize, message: impl Into<String>) -> Self {
+        AocError::Parse {
+            line,
+            column: Some(column),
+            message: message.into(),
+        }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        AocError::Validation {
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Io { path, source } => write!(f, "Couldn't read '{path}': {source}"),
+            AocError::Argument { message } => write!(f, "Invalid arguments: {message}"),
+            AocError::Parse {
+                line,
+                column: None,
+                message,
+            } => write!(f, "Parse error on line {line}: {message}"),
+            AocError::Parse {
+                line,
+                column: Some(column),
+                message,
+            } => write!(f, "Parse error on line {line}, column {column}: {message}"),
+            AocError::Validation { message } => write!(f, "Invalid input: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_includes_context() {
+        let err = AocError::Io {
+            path: "inputs/missing".to_string(),
+            source: std::io::Error::from(std::io::ErrorKind::NotFound),
+        };
+        assert!(err.to_string().contains("inputs/missing"));
+        assert_eq!(
+            AocError::parse(4, "expected a number").to_string(),
+            "Parse error on line 4: expected a number"
+        );
+        assert_eq!(
+            AocError::parse_at(4, 7, "expected a number").to_string(),
+            "Parse error on line 4, column 7: expected a number"
+        );
+        assert_eq!(
+            AocError::argument("missing --day").to_string(),
+            "Invalid arguments: missing --day"
+        );
+        assert_eq!(
+            AocError::validation("no guard").to_string(),
+            "Invalid input: no guard"
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use std::fmt::Display;
+
+use crate::grid::{Offset, Position};
+
+// Typed directions and points for grid puzzles, so that moving around is never done with raw
+// index arithmetic. Rows grow downwards, so Up is (-1, 0) and Right is (0, 1).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    // Clockwise from up.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::UpRight,
+        Direction::DownRight,
+        Direction::DownLeft,
+        Direction::UpLeft,
+    ];
+    // Clockwise from up, in 45 degree steps.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    fn from_eighths(eighths: usize) -> Self {
+        Direction::ALL[eighths % 8]
+    }
+
+    fn eighths(self) -> usize {
+        self as usize
+    }
+
+    // The (row, col) change of one step in this direction.
+    pub fn offset(self) -> Offset {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::UpRight => (-1, 1),
+            Direction::Right => (0, 1),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (1, 0),
+            Direction::DownLeft => (1, -1),
+            Direction::Left => (0, -1),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    // A quarter turn clockwise, e.g. Up to Right or UpRight to DownRight.
+    pub fn rotate_cw(self) -> Self {
+        Direction::from_eighths(self.eighths() + 2)
+    }
+
+    // A quarter turn counter-clockwise, e.g. Up to Left.
+    pub fn rotate_ccw(self) -> Self {
+        Direction::from_eighths(self.eighths() + 6)
+    }
+
+    pub fn opposite(self) -> Self {
+        Direction::from_eighths(self.eighths() + 4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.eighths() % 2 == 1
+    }
+
+    // The usual puzzle arrows: '^', '>', 'v' and '<'.
+    pub fn from_arrow(arrow: char) -> Option<Self> {
+        match arrow {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    // Diagonals don't have an arrow.
+    pub fn arrow(self) -> Option<char> {
+        match self {
+            Direction::Up => Some('^'),
+            Direction::Right => Some('>'),
+            Direction::Down => Some('v'),
+            Direction::Left => Some('<'),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Point {
+    pub fn new(row: usize, col: usize) -> Self {
+        Point { row, col }
+    }
+
+    // The point one step away, or None if that would go above row 0 or left of column 0. A point
+    // doesn't know how big its grid is, so use Grid::step to also stop at the far edges.
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        self.offset(direction.offset())
+    }
+
+    // The point `steps` steps away, with the same checks as step.
+    pub fn step_by(self, direction: Direction, steps: usize) -> Option<Self> {
+        let (row_delta, col_delta) = direction.offset();
+        let steps = isize::try_from(steps).ok()?;
+        self.offset((row_delta.checked_mul(steps)?, col_delta.checked_mul(steps)?))
+    }
+
+    pub fn offset(self, (row_delta, col_delta): Offset) -> Option<Self> {
+        Some(Point {
+            row: self.row.checked_add_signed(row_delta)?,
+            col: self.col.checked_add_signed(col_delta)?,
+        })
+    }
+}
+
+impl From<Position> for Point {
+    fn from((row, col): Position) -> Self {
+        Point { row, col }
+    }
+}
+
+impl From<Point> for Position {
+    fn from(point: Point) -> Self {
+        (point.row, point.col)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation() {
+        assert_eq!(Direction::Up.rotate_cw(), Direction::Right);
+        assert_eq!(Direction::Left.rotate_cw(), Direction::Up);
+        assert_eq!(Direction::UpRight.rotate_cw(), Direction::DownRight);
+        assert_eq!(Direction::Up.rotate_ccw(), Direction::Left);
+        assert_eq!(Direction::DownLeft.rotate_ccw(), Direction::DownRight);
+        assert_eq!(Direction::UpLeft.opposite(), Direction::DownRight);
+        for direction in Direction::ALL {
+            assert_eq!(direction.rotate_cw().rotate_ccw(), direction);
+            assert_eq!(direction.rotate_cw().rotate_cw(), direction.opposite());
+            assert_eq!(direction.opposite().opposite(), direction);
+            let (row, col) = direction.offset();
+            assert_eq!(direction.opposite().offset(), (-row, -col));
+            assert_eq!(direction.is_diagonal(), row != 0 && col != 0);
+        }
+    }
+
+    #[test]
+    fn test_arrows() {
+        for direction in Direction::CARDINAL {
+            assert_eq!(
+                Direction::from_arrow(direction.arrow().unwrap()),
+                Some(direction)
+            );
+        }
+        assert!(Direction::DIAGONAL.iter().all(|d| d.arrow().is_none()));
+        assert_eq!(Direction::from_arrow('#'), None);
+    }
+
+    #[test]
+    fn test_checked_step() {
+        let origin = Point::new(0, 0);
+        assert_eq!(origin.step(Direction::Up), None);
+        assert_eq!(origin.step(Direction::Left), None);
+        assert_eq!(origin.step(Direction::DownRight), Some(Point::new(1, 1)));
+        let point = Point::new(2, 5);
+        assert_eq!(point.step(Direction::UpLeft), Some(Point::new(1, 4)));
+        assert_eq!(point.step_by(Direction::Up, 2), Some(Point::new(0, 5)));
+        assert_eq!(point.step_by(Direction::Up, 3), None);
+        assert_eq!(point.step_by(Direction::Right, 0), Some(point));
+        assert_eq!(Position::from(point), (2, 5));
+        assert_eq!(point.to_string(), "(2, 5)");
+    }
+}
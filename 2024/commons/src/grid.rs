@@ -0,0 +1,402 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::{Direction, Point};
+use crate::AocError;
+
+// A rectangular grid of cells, stored row by row in a flat vector. Positions are (row, col) with
+// (0, 0) at the top left, and offsets are (row delta, col delta). Stepping off any edge gives None
+// instead of wrapping into the next row, so callers never have to check bounds themselves.
+
+pub type Position = (usize, usize);
+pub type Offset = (isize, isize);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    // A grid with every cell set to `fill`. Like `from_rows`, it can't be empty.
+    pub fn new(width: usize, height: usize, fill: T) -> Result<Self, AocError> {
+        if width == 0 || height == 0 {
+            return Err(AocError::validation(format!(
+                "a grid can't be {width} by {height}, both sides need at least one cell"
+            )));
+        }
+        Ok(Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        })
+    }
+}
+
+impl<T> Grid<T> {
+    // Build a grid from its rows, which must all have the same (non-zero) length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, AocError> {
+        let width = match rows.first() {
+            Some(row) if !row.is_empty() => row.len(),
+            _ => return Err(AocError::validation("the grid is empty")),
+        };
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (row_idx, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(AocError::parse(
+                    row_idx + 1,
+                    format!("expected a row of {} cells, found {}", width, row.len()),
+                ));
+            }
+            cells.extend(row);
+        }
+        Ok(Grid {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    // Parse one cell per character, one row per line. Parse errors report the line and column of
+    // the offending character.
+    pub fn parse_with<F>(text: &str, mut parse_cell: F) -> Result<Self, AocError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut rows = Vec::new();
+        for (line_idx, line) in text.lines().enumerate() {
+            let mut row = Vec::with_capacity(line.len());
+            for (col_idx, c) in line.chars().enumerate() {
+                match parse_cell(c) {
+                    Some(cell) => row.push(cell),
+                    None => {
+                        return Err(AocError::parse_at(
+                            line_idx + 1,
+                            col_idx + 1,
+                            format!("unexpected character '{c}'"),
+                        ))
+                    }
+                }
+            }
+            rows.push(row);
+        }
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // The cells in row-major order, for callers that want flat indices.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn index_of(&self, (row, col): Position) -> usize {
+        row * self.width + col
+    }
+
+    pub fn position_of(&self, index: usize) -> Position {
+        (index / self.width, index % self.width)
+    }
+
+    pub fn in_bounds(&self, (row, col): Position) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        if self.in_bounds(position) {
+            Some(&self.cells[self.index_of(position)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.in_bounds(position) {
+            let index = self.index_of(position);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    // The position `offset` away from `position`, or None if that's off the grid.
+    pub fn offset(&self, (row, col): Position, (row_delta, col_delta): Offset) -> Option<Position> {
+        let target = (
+            row.checked_add_signed(row_delta)?,
+            col.checked_add_signed(col_delta)?,
+        );
+        self.in_bounds(target).then_some(target)
+    }
+
+    // The point one step away in `direction`, or None if that's off the grid.
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        self.offset(point.into(), direction.offset())
+            .map(Point::from)
+    }
+
+    // The up/right/down/left neighbours of a position that are on the grid.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::CARDINAL
+            .into_iter()
+            .filter_map(move |direction| self.offset(position, direction.offset()))
+    }
+
+    // The neighbours of a position that are on the grid, diagonals included, clockwise from up.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.offset(position, direction.offset()))
+    }
+
+    // Every position, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |index| (index / width, index % width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Position, &mut T)> {
+        self.positions().zip(self.cells.iter_mut())
+    }
+
+    // The position of the first cell, in row-major order, that matches.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Position> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|index| self.position_of(index))
+    }
+
+    // The cells of one row, left to right. A row off the grid has no cells.
+    pub fn row(&self, row: usize) -> impl Iterator<Item = &T> {
+        let cells = if row < self.height {
+            &self.cells[row * self.width..(row + 1) * self.width]
+        } else {
+            &[]
+        };
+        cells.iter()
+    }
+
+    // The cells of one column, top to bottom. A column off the grid has no cells.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        let start = if col < self.width { col } else { self.len() };
+        self.cells[start..].iter().step_by(self.width.max(1))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    // The cells from `start` stepping by `offset` until the edge of the grid, start included.
+    pub fn ray(&self, start: Position, offset: Offset) -> impl Iterator<Item = (Position, &T)> {
+        let first = self.in_bounds(start).then_some(start);
+        std::iter::successors(first, move |position| self.offset(*position, offset))
+            .map(|position| (position, &self[position]))
+    }
+
+    // The cells from `start` down and to the right.
+    pub fn diagonal(&self, start: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.ray(start, (1, 1))
+    }
+
+    // The cells from `start` down and to the left.
+    pub fn anti_diagonal(&self, start: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.ray(start, (1, -1))
+    }
+}
+
+impl Grid<char> {
+    // The usual puzzle map: one character per cell.
+    pub fn parse(text: &str) -> Result<Self, AocError> {
+        Grid::parse_with(text, Some)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        assert!(
+            self.in_bounds(position),
+            "{position:?} is outside of a {}x{} grid",
+            self.width,
+            self.height
+        );
+        &self.cells[self.index_of(position)]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        assert!(
+            self.in_bounds(position),
+            "{position:?} is outside of a {}x{} grid",
+            self.width,
+            self.height
+        );
+        let index = self.index_of(position);
+        &mut self.cells[index]
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        &self[Position::from(point)]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        &mut self[Position::from(point)]
+    }
+}
+
+// One line per row, with each cell's Display output back to back.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRID: &str = "abc\ndef\nghi\njkl\n";
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid = Grid::parse(GRID).unwrap();
+        assert_eq!((grid.width(), grid.height(), grid.len()), (3, 4, 12));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((4, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.to_string(), GRID);
+        assert_eq!(grid.find(|c| *c == 'h'), Some((2, 1)));
+        assert_eq!(grid.position_of(grid.index_of((3, 1))), (3, 1));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            Grid::parse("abc\nde\n"),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+        assert!(matches!(Grid::parse(""), Err(AocError::Validation { .. })));
+        let digits = Grid::parse_with("12\n3x\n", |c| c.to_digit(10));
+        match digits {
+            Err(AocError::Parse { line, column, .. }) => {
+                assert_eq!((line, column), (2, Some(2)));
+            }
+            other => panic!("Expected a parse error, got {other:?}"),
+        }
+        let digits = Grid::parse_with("12\n34\n", |c| c.to_digit(10)).unwrap();
+        assert_eq!(digits[(1, 0)], 3);
+        assert!(matches!(
+            Grid::new(0, 3, 0),
+            Err(AocError::Validation { .. })
+        ));
+        assert!(matches!(
+            Grid::new(3, 0, 0),
+            Err(AocError::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_offset_does_not_wrap() {
+        let grid = Grid::parse(GRID).unwrap();
+        assert_eq!(grid.offset((1, 1), (1, -1)), Some((2, 0)));
+        // Stepping right off the end of a row must not land at the start of the next one.
+        assert_eq!(grid.offset((0, 2), (0, 1)), None);
+        assert_eq!(grid.offset((1, 0), (0, -1)), None);
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((3, 0), (1, 0)), None);
+        assert_eq!(
+            grid.step(Point::new(1, 2), Direction::DownLeft),
+            Some(Point::new(2, 1))
+        );
+        assert_eq!(grid.step(Point::new(1, 2), Direction::Right), None);
+        assert_eq!(grid[Point::new(2, 1)], 'h');
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 3, 0).unwrap();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbours8((0, 2)).collect::<Vec<_>>(),
+            vec![(1, 2), (1, 1), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_iterators() {
+        let mut grid = Grid::parse(GRID).unwrap();
+        assert_eq!(grid.row(1).collect::<String>(), "def");
+        assert_eq!(grid.column(2).collect::<String>(), "cfil");
+        // Off the grid, rather than wrapping onto other cells.
+        assert_eq!(grid.row(4).count(), 0);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.column(7).count(), 0);
+        assert_eq!(grid.column(100).count(), 0);
+        assert_eq!(
+            grid.diagonal((0, 0)).map(|(_, c)| c).collect::<String>(),
+            "aei"
+        );
+        assert_eq!(
+            grid.anti_diagonal((1, 2))
+                .map(|(_, c)| c)
+                .collect::<String>(),
+            "fhj"
+        );
+        assert_eq!(
+            grid.ray((3, 2), (-1, 0))
+                .map(|(_, c)| c)
+                .collect::<String>(),
+            "lifc"
+        );
+        assert_eq!(grid.ray((9, 9), (1, 1)).count(), 0);
+        assert_eq!(grid.rows().count(), 4);
+        assert_eq!(grid.iter().nth(4), Some(((1, 1), &'e')));
+
+        *grid.get_mut((0, 0)).unwrap() = 'z';
+        grid.iter_mut()
+            .filter(|(position, _)| position.1 == 1)
+            .for_each(|(_, c)| *c = '.');
+        grid[(3, 2)] = 'y';
+        assert_eq!(grid.positions().last(), Some((3, 2)));
+        assert_eq!(grid.to_string(), "z.c\nd.f\ng.i\nj.y\n");
+    }
+}
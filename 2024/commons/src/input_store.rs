@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::io_utilities::read_file_to_string;
+use crate::AocError;
+
+// Finds puzzle inputs by (year, day, kind) instead of by a path relative to wherever a binary or
+// test happens to be run from. Lookups try, in order:
+//  1. An entry in the manifest file `<root>/input_manifest.txt`, if there is one.
+//  2. The canonical layout `<root>/<year>/inputs/day<day>/<kind>.txt`, e.g.
+//     `2024/inputs/day6/example1.txt` or `2024/inputs/day6/full.txt`.
+//  3. The older per-crate layouts, e.g. `2023/day1/inputs/input` or `2023/day3/src/inputs/test`.
+//
+// The root defaults to the repository checkout commons was built from, and can be moved with the
+// AOC_INPUT_ROOT environment variable.
+
+pub const INPUT_ROOT_ENV_VAR: &str = "AOC_INPUT_ROOT";
+const MANIFEST_FILE: &str = "input_manifest.txt";
+const DEFAULT_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../..");
+
+// Examples sort before the full input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum InputKind {
+    // The n-th example from the puzzle text, starting at 1.
+    Example(u32),
+    // The personal puzzle input.
+    Full,
+}
+
+impl Display for InputKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputKind::Example(n) => write!(f, "example{n}"),
+            InputKind::Full => write!(f, "full"),
+        }
+    }
+}
+
+impl FromStr for InputKind {
+    type Err = AocError;
+
+    // Accepts "full", "example" (meaning example 1) and "exampleN".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(InputKind::Full),
+            "example" => Ok(InputKind::Example(1)),
+            _ => s
+                .strip_prefix("example")
+                .and_then(|n| n.parse::<u32>().ok())
+                .filter(|n| *n > 0)
+                .map(InputKind::Example)
+                .ok_or_else(|| {
+                    AocError::validation(format!(
+                        "'{s}' is not an input kind, expected full, example or exampleN"
+                    ))
+                }),
+        }
+    }
+}
+
+pub struct InputStore {
+    root: PathBuf,
+    manifest: HashMap<(u32, u32, InputKind), PathBuf>,
+}
+
+// Parse manifest lines of the form `<year> <day> <kind> <path>`, where the path is relative to the
+// store root. Blank lines and lines starting with '#' are ignored.
+fn parse_manifest(contents: &str) -> Result<HashMap<(u32, u32, InputKind), PathBuf>, AocError> {
+    let mut manifest = HashMap::new();
+    for (idx, line) in contents.lines().enumerate() {
+        let line_number = idx + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [year, day, kind, path] = fields[..] else {
+            return Err(AocError::parse(
+                line_number,
+                format!(
+                    "expected '<year> <day> <kind> <path>', found {} fields",
+                    fields.len()
+                ),
+            ));
+        };
+        let number = |field: &str, what: &str| {
+            field.parse::<u32>().map_err(|_| {
+                AocError::parse(line_number, format!("'{field}' is not a valid {what}"))
+            })
+        };
+        let kind = kind
+            .parse::<InputKind>()
+            .map_err(|err| AocError::parse(line_number, err.to_string()))?;
+        manifest.insert(
+            (number(year, "year")?, number(day, "day")?, kind),
+            PathBuf::from(path),
+        );
+    }
+    Ok(manifest)
+}
+
+impl InputStore {
+    // Open a store rooted at `root`, loading its manifest if it has one.
+    pub fn new(root: impl Into<PathBuf>) -> Result<Self, AocError> {
+        let root = root.into();
+        let manifest_path = root.join(MANIFEST_FILE);
+        let manifest = if manifest_path.is_file() {
+            parse_manifest(&read_file_to_string(&manifest_path.to_string_lossy())?)?
+        } else {
+            HashMap::new()
+        };
+        Ok(InputStore { root, manifest })
+    }
+
+    // Open the store at AOC_INPUT_ROOT, or at the repository root if that isn't set.
+    pub fn from_env() -> Result<Self, AocError> {
+        match std::env::var(INPUT_ROOT_ENV_VAR) {
+            Ok(root) => InputStore::new(root),
+            Err(_) => {
+                let root = Path::new(DEFAULT_ROOT);
+                InputStore::new(root.canonicalize().unwrap_or(root.to_path_buf()))
+            }
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    // Every path that could hold the input, in the order they are tried.
+    pub fn candidates(&self, year: u32, day: u32, kind: InputKind) -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
+        if let Some(path) = self.manifest.get(&(year, day, kind)) {
+            candidates.push(self.root.join(path));
+        }
+        candidates.push(
+            self.root
+                .join(year.to_string())
+                .join("inputs")
+                .join(format!("day{day}"))
+                .join(format!("{kind}.txt")),
+        );
+        let day_dir = self.root.join(year.to_string()).join(format!("day{day}"));
+        let legacy_names: Vec<String> = match kind {
+            InputKind::Full => [
+                "inputs/input",
+                "inputs/full",
+                "src/inputs/full",
+                "input/full",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+            InputKind::Example(1) => [
+                "test_input",
+                "inputs/test",
+                "src/inputs/test",
+                "input/test",
+                "inputs/small_input",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+            InputKind::Example(n) => vec![format!("test_input{n}"), format!("inputs/test{n}")],
+        };
+        candidates.extend(legacy_names.iter().map(|name| day_dir.join(name)));
+        candidates
+    }
+
+    // The first candidate path that exists.
+    pub fn resolve(&self, year: u32, day: u32, kind: InputKind) -> Result<PathBuf, AocError> {
+        let candidates = self.candidates(year, day, kind);
+        candidates
+            .iter()
+            .find(|path| path.is_file())
+            .cloned()
+            .ok_or_else(|| {
+                let tried: Vec<String> = candidates
+                    .iter()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .collect();
+                AocError::Io {
+                    path: format!("{year} day {day} {kind}"),
+                    source: std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!("no input found, tried {}", tried.join(", ")),
+                    ),
+                }
+            })
+    }
+
+    pub fn read(&self, year: u32, day: u32, kind: InputKind) -> Result<String, AocError> {
+        let path = self.resolve(year, day, kind)?;
+        read_file_to_string(&path.to_string_lossy())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A throwaway store root under the system temp dir, removed when dropped.
+    struct TempRoot(PathBuf);
+
+    impl TempRoot {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!(
+                "aoc_input_store_{}_{}",
+                name,
+                std::process::id()
+            ));
+            let _ = std::fs::remove_dir_all(&root);
+            std::fs::create_dir_all(&root).unwrap();
+            TempRoot(root)
+        }
+
+        fn write(&self, relative_path: &str, contents: &str) {
+            let path = self.0.join(relative_path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+    }
+
+    impl Drop for TempRoot {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_input_kind_round_trip() {
+        assert_eq!("full".parse::<InputKind>().unwrap(), InputKind::Full);
+        assert_eq!(
+            "example".parse::<InputKind>().unwrap(),
+            InputKind::Example(1)
+        );
+        assert_eq!(
+            "example3".parse::<InputKind>().unwrap(),
+            InputKind::Example(3)
+        );
+        assert!("example0".parse::<InputKind>().is_err());
+        assert!("sample".parse::<InputKind>().is_err());
+        assert_eq!(InputKind::Example(2).to_string(), "example2");
+        assert_eq!(InputKind::Full.to_string(), "full");
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest =
+            parse_manifest("# comment\n\n2023 8 example2 2023/day8/inputs/test_with_loop\n")
+                .unwrap();
+        assert_eq!(
+            manifest.get(&(2023, 8, InputKind::Example(2))),
+            Some(&PathBuf::from("2023/day8/inputs/test_with_loop"))
+        );
+        assert!(matches!(
+            parse_manifest("2023 8 example2\n"),
+            Err(AocError::Parse { line: 1, .. })
+        ));
+        assert!(matches!(
+            parse_manifest("\n2023 eight full a/b\n"),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_resolution_order() {
+        let root = TempRoot::new("resolution_order");
+        root.write("2023/day3/src/inputs/test", "legacy");
+        let store = InputStore::new(&root.0).unwrap();
+        assert_eq!(
+            store.read(2023, 3, InputKind::Example(1)).unwrap(),
+            "legacy"
+        );
+
+        // The canonical layout wins over the legacy one.
+        root.write("2023/inputs/day3/example1.txt", "canonical");
+        assert_eq!(
+            store.read(2023, 3, InputKind::Example(1)).unwrap(),
+            "canonical"
+        );
+
+        // And the manifest wins over both. It is read when the store is opened.
+        root.write(MANIFEST_FILE, "2023 3 example1 elsewhere/ex\n");
+        root.write("elsewhere/ex", "manifest");
+        let store = InputStore::new(&root.0).unwrap();
+        assert_eq!(
+            store.read(2023, 3, InputKind::Example(1)).unwrap(),
+            "manifest"
+        );
+    }
+
+    #[test]
+    fn test_missing_input() {
+        let root = TempRoot::new("missing_input");
+        let store = InputStore::new(&root.0).unwrap();
+        match store.resolve(2024, 6, InputKind::Full) {
+            Err(AocError::Io { path, source }) => {
+                assert_eq!(path, "2024 day 6 full");
+                assert!(source.to_string().contains("full.txt"));
+            }
+            other => panic!("Expected an I/O error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_repository_inputs() {
+        // The default root is this repository, so its committed inputs are always found.
+        let store = InputStore::new(DEFAULT_ROOT).unwrap();
+        assert!(store.resolve(2024, 6, InputKind::Example(1)).is_ok());
+        assert!(store.resolve(2023, 1, InputKind::Full).is_ok());
+        assert!(store.resolve(2023, 8, InputKind::Example(3)).is_ok());
+    }
+}
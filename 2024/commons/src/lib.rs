@@ -1,24 +1,43 @@
-mod list;
+pub mod answers;
+pub mod arg_parsing;
+mod error;
+pub mod geometry;
+pub mod grid;
+pub mod input_store;
+pub mod list;
+pub mod parse;
+pub mod runner;
+pub mod solution;
+pub mod test_utils;
+
+pub use error::AocError;
 
 pub mod io_utilities {
-    pub fn read_file_to_string(file_name: &String) -> String {
-        std::fs::read_to_string(file_name).unwrap()
+    use crate::AocError;
+
+    pub fn read_file_to_string(file_name: &str) -> Result<String, AocError> {
+        std::fs::read_to_string(file_name).map_err(|source| AocError::Io {
+            path: file_name.to_string(),
+            source,
+        })
     }
 
-    pub fn read_file_lines(file_name: &String) -> Vec<String> {
-        let file_string = read_file_to_string(file_name);
-        file_string.lines().map(|line| line.to_string()).collect()
+    pub fn read_file_lines(file_name: &str) -> Result<Vec<String>, AocError> {
+        let file_string = read_file_to_string(file_name)?;
+        Ok(file_string.lines().map(|line| line.to_string()).collect())
     }
-}
 
-pub mod arg_parsing {
-    pub fn get_file_name_or_quit() -> String {
-        let args: Vec<String> = std::env::args().collect();
-        let arg_len = args.len();
-        if arg_len < 2 {
-            println!("Provided args didn't have a filename! args = {:?}", args);
-            quit::with_code(1);
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_missing_file_is_an_io_error() {
+            match read_file_to_string("does/not/exist") {
+                Err(AocError::Io { path, .. }) => assert_eq!(path, "does/not/exist"),
+                other => panic!("Expected an I/O error, got {:?}", other),
+            }
+            assert!(read_file_lines("does/not/exist").is_err());
         }
-        args[arg_len - 1].clone()
     }
 }
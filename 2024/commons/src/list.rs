@@ -1,24 +1,47 @@
+use std::fmt::Debug;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::mem;
+use std::ptr::NonNull;
+
+// A doubly-linked list. Every node points at both of its neighbours, and the list keeps pointers
+// to both ends, so pushing and popping at either end and appending another list are all O(1).
+//
+// Nodes are boxed and then held through raw pointers, since a node with two owners can't be
+// expressed with Box alone. The list owns every node: they're only ever freed by the pop functions
+// (and so by clear and drop), which turn them back into a Box.
 pub struct List<T> {
     head: Link<T>,
     tail: Link<T>,
     len: usize,
+    // The list owns its nodes even though it only holds pointers to them.
+    _owns: PhantomData<Box<Node<T>>>,
 }
 
-type Link<T> = Option<Box<Node<T>>>;
-
-pub trait Iterator {
-    type Item;
-    fn next(&mut self) -> Option<Self::Item>;
-}
+type Link<T> = Option<NonNull<Node<T>>>;
 
 struct Node<T> {
     elem: T,
     next: Link<T>,
+    prev: Link<T>,
 }
 
 pub struct IntoIter<T>(List<T>);
+
+// The borrowing iterators walk in from both ends, and count the elements left between them so
+// that they know when the two ends meet.
 pub struct Iter<'a, T> {
-    next: Option<&'a Node<T>>,
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    _borrow: PhantomData<&'a T>,
+}
+
+pub struct IterMut<'a, T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    _borrow: PhantomData<&'a mut T>,
 }
 
 impl<T> Iterator for IntoIter<T> {
@@ -27,99 +50,285 @@ impl<T> Iterator for IntoIter<T> {
         // Tuple struct accesses the raw underlying list with .0, then call pop_front
         self.0.pop_front()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
 }
 
 impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
     fn next(&mut self) -> Option<Self::Item> {
-        // Access the next node in the list, and then update the next field to the next node
-        if let Some(node) = self.next {
-            self.next = node.next.as_deref();
-            return Some(&node.elem);
+        // Access the next node in the list, and then update the head field to the next node
+        if self.len == 0 {
+            return None;
         }
-        None
+        self.head.map(|node| unsafe {
+            // The list is borrowed for 'a, so none of its nodes can be freed or changed meanwhile.
+            let node = &*node.as_ptr();
+            self.len -= 1;
+            self.head = node.next;
+            &node.elem
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
     }
 }
 
-impl<T> List<T> {
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter(self)
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|node| unsafe {
+            let node = &*node.as_ptr();
+            self.len -= 1;
+            self.tail = node.prev;
+            &node.elem
+        })
     }
+}
 
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|node| unsafe {
+            // The list is mutably borrowed for 'a, and each node is handed out at most once,
+            // since the len check stops the two ends from passing each other.
+            let node = &mut *node.as_ptr();
+            self.len -= 1;
+            self.head = node.next;
+            &mut node.elem
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|node| unsafe {
+            let node = &mut *node.as_ptr();
+            self.len -= 1;
+            self.tail = node.prev;
+            &mut node.elem
+        })
+    }
+}
+
+// A cursor for editing the list in place. It points either at an element or at the "ghost"
+// position, which sits between the back and the front of the list: moving next from the ghost
+// goes to the front, and moving next from the back goes to the ghost. Every edit is O(1) except
+// that splitting and splicing also keep the cursor's index up to date.
+pub struct CursorMut<'a, T> {
+    list: &'a mut List<T>,
+    cur: Link<T>,
+    index: Option<usize>,
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+impl<T> FusedIterator for IntoIter<T> {}
+impl<T> FusedIterator for Iter<'_, T> {}
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+impl<T> Node<T> {
+    // Move a new node onto the heap. It's freed again by List::unlink.
+    fn allocate(elem: T) -> NonNull<Node<T>> {
+        NonNull::from(Box::leak(Box::new(Node {
+            elem,
+            next: None,
+            prev: None,
+        })))
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
+}
+
+impl<T> List<T> {
     pub fn iter(&self) -> Iter<'_, T> {
         Iter {
-            //next: self.head.as_ref().map::<&Node<T>, _>(|node| node),
-            next: self.head.as_deref(),
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            _borrow: PhantomData,
         }
     }
 
-    fn move_all_elements_out(&mut self) {
-        // move out of the head first, and then the node will be auto-dropped when the
-        // function scope ends
-        let mut dropped_head = self.head.take();
-        while let Some(mut dropped_next) = dropped_head {
-            dropped_head = dropped_next.next.take();
+    // A cursor at the ghost position, so that move_next goes to the front.
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            list: self,
+            cur: None,
+            index: None,
         }
     }
 
-    fn new() -> Self {
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        let index = self.head.map(|_| 0);
+        CursorMut {
+            cur: self.head,
+            list: self,
+            index,
+        }
+    }
+
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        let index = self.tail.map(|_| self.len - 1);
+        CursorMut {
+            cur: self.tail,
+            list: self,
+            index,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            _borrow: PhantomData,
+        }
+    }
+
+    fn move_all_elements_out(&mut self) {
+        // Pop every node so that each one is freed, along with its element.
+        while self.pop_front().is_some() {}
+    }
+
+    pub fn new() -> Self {
         List {
             head: None,
             tail: None,
             len: 0,
+            _owns: PhantomData,
         }
     }
 
-    fn push_front(&mut self, elem: T) {
-        let new = Box::new(Node {
-            elem,
-            next: self.head.take(),
-        });
+    pub fn push_front(&mut self, elem: T) {
+        let new = Node::allocate(elem);
+        unsafe {
+            match self.head {
+                Some(old_head) => {
+                    (*old_head.as_ptr()).prev = Some(new);
+                    (*new.as_ptr()).next = Some(old_head);
+                }
+                None => self.tail = Some(new),
+            }
+        }
         self.head = Some(new);
         self.len += 1;
     }
 
-    fn pop_front(&mut self) -> Option<T> {
-        // Return the elem in the head (moved out).
-        // Set the current head to the old head's "next".
-        if let Some(popped) = self.head.take() {
-            assert!(self.head.is_none());
-            self.head = popped.next;
-            self.len -= 1;
-            Some(popped.elem)
-        } else {
-            None
+    pub fn push_back(&mut self, elem: T) {
+        let new = Node::allocate(elem);
+        unsafe {
+            match self.tail {
+                Some(old_tail) => {
+                    (*old_tail.as_ptr()).next = Some(new);
+                    (*new.as_ptr()).prev = Some(old_tail);
+                }
+                None => self.head = Some(new),
+            }
         }
+        self.tail = Some(new);
+        self.len += 1;
     }
 
-    fn front(&self) -> Option<&T> {
-        // returns a reference to the list head element
-        match self.head.as_ref() {
-            Some(x) => Some(&x.elem),
-            None => None,
+    // Take a node out of the list, fix up its neighbours (or the ends of the list) and free it.
+    // The node must belong to this list.
+    unsafe fn unlink(&mut self, node: NonNull<Node<T>>) -> T {
+        let boxed = Box::from_raw(node.as_ptr());
+        match boxed.prev {
+            Some(prev) => (*prev.as_ptr()).next = boxed.next,
+            None => self.head = boxed.next,
+        }
+        match boxed.next {
+            Some(next) => (*next.as_ptr()).prev = boxed.prev,
+            None => self.tail = boxed.prev,
         }
+        self.len -= 1;
+        boxed.elem
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        // Return the elem in the head (moved out).
+        // The old head's "next" becomes the new head.
+        self.head.map(|head| unsafe { self.unlink(head) })
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.map(|tail| unsafe { self.unlink(tail) })
     }
 
-    fn front_mut(&mut self) -> Option<&mut T> {
-        // return an immutable reference to the list head
-        match self.head.as_mut() {
-            Some(x) => Some(&mut x.as_mut().elem),
-            None => None,
+    pub fn front(&self) -> Option<&T> {
+        // returns a reference to the list head element
+        self.head.map(|node| unsafe { &(*node.as_ptr()).elem })
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        // return a mutable reference to the list head
+        self.head.map(|node| unsafe { &mut (*node.as_ptr()).elem })
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.tail.map(|node| unsafe { &(*node.as_ptr()).elem })
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.tail.map(|node| unsafe { &mut (*node.as_ptr()).elem })
+    }
+
+    // Move all of `other`'s elements onto the back of this list, leaving `other` empty. Only the
+    // ends of the two lists are relinked, so this doesn't depend on their lengths.
+    pub fn append(&mut self, other: &mut List<T>) {
+        match self.tail {
+            None => mem::swap(self, other),
+            Some(tail) => {
+                if let Some(other_head) = other.head.take() {
+                    unsafe {
+                        (*tail.as_ptr()).next = Some(other_head);
+                        (*other_head.as_ptr()).prev = Some(tail);
+                    }
+                    self.tail = other.tail.take();
+                    self.len += mem::replace(&mut other.len, 0);
+                }
+            }
         }
     }
 
-    fn len(&self) -> usize {
+    pub fn len(&self) -> usize {
         self.len
     }
 
-    fn is_empty(&self) -> bool {
+    pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
-    fn clear(&mut self) {
+    pub fn clear(&mut self) {
         // Iteratively move all elements out of "head", the same as the implementation of drop().
         self.move_all_elements_out();
-        self.len = 0;
     }
 }
 
@@ -128,15 +337,278 @@ where
     T: PartialEq,
 {
     // Implementation for types that require PartialEq on the list generic
-    fn contains(&self, e: &T) -> bool {
-        let mut cur = &self.head;
-        while let Some(node) = cur {
-            if node.elem == *e {
-                return true;
+    pub fn contains(&self, e: &T) -> bool {
+        self.iter().any(|elem| *elem == *e)
+    }
+}
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// Collecting pushes onto the back, so the list keeps the iterator's order.
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for List<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|elem| self.push_back(elem));
+    }
+}
+
+impl<'a, T: Copy + 'a> Extend<&'a T> for List<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: Debug> Debug for List<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+impl<'a, T> CursorMut<'a, T> {
+    // The index of the current element, or None at the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.cur.map(|node| unsafe { &mut (*node.as_ptr()).elem })
+    }
+
+    fn next_node(&self) -> Link<T> {
+        match self.cur {
+            Some(cur) => unsafe { (*cur.as_ptr()).next },
+            None => self.list.head,
+        }
+    }
+
+    fn prev_node(&self) -> Link<T> {
+        match self.cur {
+            Some(cur) => unsafe { (*cur.as_ptr()).prev },
+            None => self.list.tail,
+        }
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        self.next_node()
+            .map(|node| unsafe { &mut (*node.as_ptr()).elem })
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        self.prev_node()
+            .map(|node| unsafe { &mut (*node.as_ptr()).elem })
+    }
+
+    pub fn move_next(&mut self) {
+        self.cur = self.next_node();
+        self.index = match (self.cur, self.index) {
+            (None, _) => None,
+            (Some(_), Some(index)) => Some(index + 1),
+            (Some(_), None) => Some(0),
+        };
+    }
+
+    pub fn move_prev(&mut self) {
+        self.cur = self.prev_node();
+        self.index = match (self.cur, self.index) {
+            (None, _) => None,
+            (Some(_), Some(index)) => Some(index - 1),
+            (Some(_), None) => Some(self.list.len - 1),
+        };
+    }
+
+    // Insert after the current element. At the ghost position that's the front of the list.
+    pub fn insert_after(&mut self, elem: T) {
+        match (self.cur, self.next_node()) {
+            (Some(cur), Some(next)) => unsafe {
+                let new = Node::allocate(elem);
+                (*new.as_ptr()).prev = Some(cur);
+                (*new.as_ptr()).next = Some(next);
+                (*cur.as_ptr()).next = Some(new);
+                (*next.as_ptr()).prev = Some(new);
+                self.list.len += 1;
+            },
+            (Some(_), None) => self.list.push_back(elem),
+            (None, _) => self.list.push_front(elem),
+        }
+    }
+
+    // Insert before the current element. At the ghost position that's the back of the list.
+    pub fn insert_before(&mut self, elem: T) {
+        match (self.cur, self.prev_node()) {
+            (Some(cur), Some(prev)) => unsafe {
+                let new = Node::allocate(elem);
+                (*new.as_ptr()).prev = Some(prev);
+                (*new.as_ptr()).next = Some(cur);
+                (*cur.as_ptr()).prev = Some(new);
+                (*prev.as_ptr()).next = Some(new);
+                self.list.len += 1;
+            },
+            (Some(_), None) => self.list.push_front(elem),
+            (None, _) => self.list.push_back(elem),
+        }
+        // The current element moved along by one.
+        self.index = self.index.map(|index| index + 1);
+    }
+
+    // Remove the current element and move on to the next one, which takes over its index.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let cur = self.cur?;
+        self.cur = self.next_node();
+        if self.cur.is_none() {
+            self.index = None;
+        }
+        // The node belongs to this list, and the cursor no longer points at it.
+        Some(unsafe { self.list.unlink(cur) })
+    }
+
+    // Split the list after the current element, returning everything after it. At the ghost
+    // position that's the whole list.
+    pub fn split_after(&mut self) -> List<T> {
+        let (Some(cur), Some(index)) = (self.cur, self.index) else {
+            return mem::take(self.list);
+        };
+        let Some(next) = self.next_node() else {
+            return List::new();
+        };
+        unsafe {
+            (*cur.as_ptr()).next = None;
+            (*next.as_ptr()).prev = None;
+        }
+        let split = List {
+            head: Some(next),
+            tail: self.list.tail,
+            len: self.list.len - index - 1,
+            _owns: PhantomData,
+        };
+        self.list.tail = Some(cur);
+        self.list.len = index + 1;
+        split
+    }
+
+    // Split the list before the current element, returning everything before it. At the ghost
+    // position that's the whole list.
+    pub fn split_before(&mut self) -> List<T> {
+        let (Some(cur), Some(index)) = (self.cur, self.index) else {
+            return mem::take(self.list);
+        };
+        let Some(prev) = self.prev_node() else {
+            return List::new();
+        };
+        unsafe {
+            (*cur.as_ptr()).prev = None;
+            (*prev.as_ptr()).next = None;
+        }
+        let split = List {
+            head: self.list.head,
+            tail: Some(prev),
+            len: index,
+            _owns: PhantomData,
+        };
+        self.list.head = Some(cur);
+        self.list.len -= index;
+        self.index = Some(0);
+        split
+    }
+
+    // Move all of `other`'s elements in after the current element. At the ghost position they go
+    // on the front of the list.
+    pub fn splice_after(&mut self, mut other: List<T>) {
+        let (Some(other_head), Some(other_tail)) = (other.head.take(), other.tail.take()) else {
+            return;
+        };
+        let other_len = mem::replace(&mut other.len, 0);
+        let next = self.next_node();
+        unsafe {
+            match self.cur {
+                Some(cur) => {
+                    (*cur.as_ptr()).next = Some(other_head);
+                    (*other_head.as_ptr()).prev = Some(cur);
+                }
+                None => self.list.head = Some(other_head),
+            }
+            match next {
+                Some(next) => {
+                    (*next.as_ptr()).prev = Some(other_tail);
+                    (*other_tail.as_ptr()).next = Some(next);
+                }
+                None => self.list.tail = Some(other_tail),
             }
-            cur = &node.next;
         }
-        false
+        self.list.len += other_len;
+    }
+
+    // Move all of `other`'s elements in before the current element. At the ghost position they go
+    // on the back of the list.
+    pub fn splice_before(&mut self, mut other: List<T>) {
+        let (Some(other_head), Some(other_tail)) = (other.head.take(), other.tail.take()) else {
+            return;
+        };
+        let other_len = mem::replace(&mut other.len, 0);
+        let prev = self.prev_node();
+        unsafe {
+            match self.cur {
+                Some(cur) => {
+                    (*cur.as_ptr()).prev = Some(other_tail);
+                    (*other_tail.as_ptr()).next = Some(cur);
+                }
+                None => self.list.tail = Some(other_tail),
+            }
+            match prev {
+                Some(prev) => {
+                    (*prev.as_ptr()).next = Some(other_head);
+                    (*other_head.as_ptr()).prev = Some(prev);
+                }
+                None => self.list.head = Some(other_head),
+            }
+        }
+        self.list.len += other_len;
+        self.index = self.index.map(|index| index + other_len);
     }
 }
 
@@ -146,10 +618,18 @@ impl<T> Drop for List<T> {
     }
 }
 
+// The raw pointers make the list neither Send nor Sync by default, but it owns its elements just
+// like a Vec does.
+unsafe impl<T: Send> Send for List<T> {}
+unsafe impl<T: Sync> Sync for List<T> {}
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
 #[cfg(test)]
 mod test {
 
-    use super::Iterator;
     use super::List;
     #[test]
     fn test_new_basic() {
@@ -162,10 +642,13 @@ mod test {
     fn test_push_front() {
         let mut list: List<i32> = List::new();
         list.push_front(1);
-        assert_eq!(list.head.as_ref().unwrap().elem, 1);
+        assert_eq!(list.front(), Some(&1));
         list.push_front(2);
-        assert_eq!(list.head.as_ref().unwrap().elem, 2);
-        assert_eq!(list.head.as_ref().unwrap().next.as_ref().unwrap().elem, 1);
+        assert_eq!(list.front(), Some(&2));
+        let mut iter = list.iter();
+        iter.next();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(list.back(), Some(&1));
         assert_eq!(list.len(), 2);
     }
 
@@ -258,4 +741,363 @@ mod test {
         assert_eq!(iter.next(), Some(&1));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn test_push_and_pop_back() {
+        let mut list: List<i32> = List::new();
+        assert_eq!(list.back(), None);
+        assert_eq!(list.pop_back(), None);
+        list.push_back(1);
+        list.push_back(2);
+        list.push_front(0);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.front(), Some(&0));
+        assert_eq!(list.back(), Some(&2));
+        *list.back_mut().unwrap() = 3;
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_back(), Some(1));
+        // The last element is both the front and the back.
+        assert_eq!(list.front(), Some(&0));
+        assert_eq!(list.back(), Some(&0));
+        assert_eq!(list.pop_back(), Some(0));
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+        // And the list is still usable once emptied from the back.
+        list.push_back(4);
+        assert_eq!(list.pop_front(), Some(4));
+        assert_eq!(list.back(), None);
+    }
+
+    #[test]
+    fn test_queue_order() {
+        // push_back and pop_front make a FIFO queue.
+        let mut list: List<i32> = List::new();
+        (1..=5).for_each(|n| list.push_back(n));
+        assert_eq!(list.pop_front(), Some(1));
+        list.push_back(6);
+        let mut drained = Vec::new();
+        while let Some(n) = list.pop_front() {
+            drained.push(n);
+        }
+        assert_eq!(drained, vec![2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_append() {
+        let mut list: List<i32> = List::new();
+        let mut other: List<i32> = List::new();
+        // Appending to an empty list takes the other list's nodes.
+        other.push_back(1);
+        other.push_back(2);
+        list.append(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(other.front(), None);
+        assert_eq!(
+            (list.len(), list.front(), list.back()),
+            (2, Some(&1), Some(&2))
+        );
+        // Appending an empty list changes nothing.
+        list.append(&mut other);
+        assert_eq!(list.len(), 2);
+        other.push_back(3);
+        other.push_back(4);
+        list.append(&mut other);
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.back(), Some(&4));
+        assert_eq!(list.pop_back(), Some(4));
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_back(), Some(2));
+        // other is still a working, empty list.
+        other.push_front(5);
+        assert_eq!(other.back(), Some(&5));
+    }
+
+    #[test]
+    fn test_drop_frees_every_element() {
+        use std::rc::Rc;
+        let counted = Rc::new(());
+        let mut list = List::new();
+        for _ in 0..10 {
+            list.push_back(Rc::clone(&counted));
+        }
+        assert_eq!(Rc::strong_count(&counted), 11);
+        list.pop_back();
+        list.pop_front();
+        assert_eq!(Rc::strong_count(&counted), 9);
+        drop(list);
+        assert_eq!(Rc::strong_count(&counted), 1);
+    }
+
+    #[test]
+    fn test_long_list_drop() {
+        // Dropping is iterative, so a long list doesn't overflow the stack.
+        let mut list = List::new();
+        (0..1_000_000).for_each(|n| list.push_back(n));
+        assert_eq!(list.len(), 1_000_000);
+    }
+
+    #[test]
+    fn test_std_iterator_adapters() {
+        let list: List<i32> = (1..=6).collect();
+        assert_eq!(list.len(), 6);
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(list.back(), Some(&6));
+        let evens: Vec<i32> = list.iter().filter(|n| *n % 2 == 0).copied().collect();
+        assert_eq!(evens, vec![2, 4, 6]);
+        let mut sum = 0;
+        for n in &list {
+            sum += n;
+        }
+        assert_eq!(sum, 21);
+        assert_eq!(list.iter().len(), 6);
+        let doubled: List<i32> = list.into_iter().map(|n| n * 2).collect();
+        assert_eq!(format!("{doubled:?}"), "[2, 4, 6, 8, 10, 12]");
+    }
+
+    #[test]
+    fn test_double_ended_iteration() {
+        let list: List<i32> = (1..=5).collect();
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        // The two ends have met, so both are finished.
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(
+            list.iter().rev().copied().collect::<Vec<_>>(),
+            vec![5, 4, 3, 2, 1]
+        );
+        let mut into_iter = list.clone().into_iter();
+        assert_eq!(into_iter.next_back(), Some(5));
+        assert_eq!(into_iter.next(), Some(1));
+        assert_eq!(into_iter.len(), 3);
+        assert_eq!(
+            list.into_iter().rev().collect::<Vec<_>>(),
+            vec![5, 4, 3, 2, 1]
+        );
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut list: List<i32> = (1..=4).collect();
+        list.iter_mut().for_each(|n| *n *= 10);
+        for n in &mut list {
+            *n += 1;
+        }
+        if let Some(last) = list.iter_mut().next_back() {
+            *last = 0;
+        }
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![11, 21, 31, 0]
+        );
+        let mut iter = list.iter_mut();
+        let (first, last) = (iter.next().unwrap(), iter.next_back().unwrap());
+        std::mem::swap(first, last);
+        assert_eq!(iter.len(), 2);
+        assert_eq!(list.front(), Some(&0));
+        assert_eq!(list.back(), Some(&11));
+    }
+
+    #[test]
+    fn test_extend_clone_and_eq() {
+        let mut list: List<i32> = List::new();
+        list.extend(vec![1, 2]);
+        list.extend(&[3, 4]);
+        assert_eq!(list.len(), 4);
+        let copy = list.clone();
+        assert_eq!(copy, list);
+        list.pop_back();
+        assert_ne!(copy, list);
+        assert_eq!(List::<i32>::new(), List::default());
+    }
+
+    fn to_vec(list: &List<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_cursor_navigation() {
+        let mut list: List<i32> = (1..=3).collect();
+        let mut cursor = list.cursor_mut();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 3));
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&mut 1)));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&mut 3)));
+        assert_eq!(cursor.peek_next(), None);
+        // Off the back onto the ghost, and round to the front again.
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&mut 3)));
+        *cursor.current().unwrap() = 30;
+        assert_eq!(to_vec(&list), vec![1, 2, 30]);
+
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.index(), Some(2));
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        let mut empty: List<i32> = List::new();
+        let mut cursor = empty.cursor_front_mut();
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+    }
+
+    #[test]
+    fn test_cursor_insert_and_remove() {
+        let mut list: List<i32> = List::new();
+        let mut cursor = list.cursor_mut();
+        // At the ghost, inserting after adds to the front and inserting before adds to the back.
+        cursor.insert_after(2);
+        cursor.insert_before(4);
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        cursor.insert_after(3);
+        cursor.insert_before(1);
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 4]);
+        assert_eq!(list.len(), 4);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(2));
+        // The next element takes over the removed one's index.
+        assert_eq!((cursor.index(), cursor.current()), (Some(1), Some(&mut 3)));
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(4));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+        assert_eq!(to_vec(&list), vec![1, 3]);
+        assert_eq!((list.len(), list.back()), (2, Some(&3)));
+        let mut cursor = list.cursor_front_mut();
+        cursor.remove_current();
+        cursor.remove_current();
+        assert!(list.is_empty());
+        assert_eq!((list.front(), list.back()), (None, None));
+    }
+
+    #[test]
+    fn test_cursor_split() {
+        let mut list: List<i32> = (1..=5).collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        let after = cursor.split_after();
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(to_vec(&after), vec![3, 4, 5]);
+        assert_eq!((after.len(), after.back()), (3, Some(&5)));
+        assert_eq!(to_vec(&list), vec![1, 2]);
+        assert_eq!((list.len(), list.back()), (2, Some(&2)));
+
+        let mut list: List<i32> = (1..=5).collect();
+        let mut cursor = list.cursor_back_mut();
+        cursor.move_prev();
+        let before = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(to_vec(&before), vec![1, 2, 3]);
+        assert_eq!(to_vec(&list), vec![4, 5]);
+        assert_eq!((list.len(), list.front()), (2, Some(&4)));
+
+        // Splitting at either end gives an empty list, and at the ghost takes the whole list.
+        let mut cursor = list.cursor_back_mut();
+        assert!(cursor.split_after().is_empty());
+        assert!(list.cursor_front_mut().split_before().is_empty());
+        let all = list.cursor_mut().split_after();
+        assert_eq!(to_vec(&all), vec![4, 5]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_cursor_splice() {
+        let mut list: List<i32> = vec![1, 5].into_iter().collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.splice_after((2..=4).collect());
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        cursor.splice_before(List::new());
+        cursor.splice_after(List::new());
+        assert_eq!(to_vec(&list), vec![1, 2, 3, 4, 5]);
+
+        let mut cursor = list.cursor_back_mut();
+        cursor.splice_before(vec![40, 41].into_iter().collect());
+        assert_eq!((cursor.index(), cursor.current()), (Some(6), Some(&mut 5)));
+        cursor.splice_after(vec![6].into_iter().collect());
+        // At the ghost, splicing after goes on the front and splicing before on the back.
+        let mut cursor = list.cursor_mut();
+        cursor.splice_after(vec![0].into_iter().collect());
+        cursor.splice_before(vec![7].into_iter().collect());
+        assert_eq!(to_vec(&list), vec![0, 1, 2, 3, 4, 40, 41, 5, 6, 7]);
+        assert_eq!(list.len(), 10);
+        assert_eq!(list.iter().rev().count(), 10);
+        assert_eq!((list.front(), list.back()), (Some(&0), Some(&7)));
+
+        let mut empty: List<i32> = List::new();
+        empty
+            .cursor_mut()
+            .splice_after(vec![1, 2].into_iter().collect());
+        assert_eq!(
+            (empty.front(), empty.back(), empty.len()),
+            (Some(&1), Some(&2), 2)
+        );
+    }
+
+    #[test]
+    fn test_cursor_stone_rules() {
+        // The stone rules from day 11: 0 becomes 1, a stone with an even number of digits splits
+        // into its two halves, and any other stone is multiplied by 2024.
+        let mut stones: List<u64> = vec![125, 17].into_iter().collect();
+        for _ in 0..6 {
+            let mut cursor = stones.cursor_front_mut();
+            while let Some(stone) = cursor.current() {
+                let digits = stone.to_string();
+                if *stone == 0 {
+                    *stone = 1;
+                } else if digits.len() % 2 == 0 {
+                    let (left, right) = digits.split_at(digits.len() / 2);
+                    *stone = left.parse().unwrap();
+                    cursor.insert_after(right.parse().unwrap());
+                    cursor.move_next();
+                } else {
+                    *stone *= 2024;
+                }
+                cursor.move_next();
+            }
+        }
+        assert_eq!(stones.len(), 22);
+        assert_eq!(
+            stones.iter().take(4).copied().collect::<Vec<_>>(),
+            vec![2097446912, 14168, 4048, 2]
+        );
+    }
+
+    #[test]
+    fn test_cursor_edits_free_every_element() {
+        use std::rc::Rc;
+        let counted = Rc::new(());
+        let mut list: List<Rc<()>> = (0..6).map(|_| Rc::clone(&counted)).collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        let mut after = cursor.split_after();
+        cursor.remove_current();
+        cursor.insert_before(Rc::clone(&counted));
+        after.cursor_front_mut().splice_after(list);
+        assert_eq!(Rc::strong_count(&counted), 7);
+        drop(after);
+        assert_eq!(Rc::strong_count(&counted), 1);
+    }
 }
@@ -0,0 +1,324 @@
+use std::str::FromStr;
+
+use crate::AocError;
+
+// Helpers for the usual shapes of puzzle input: blank-line separated sections, lines full of
+// numbers, delimited records and "key: value" headers. Everything works on spans, which remember
+// which line and column their text came from, so any error can point at the exact spot.
+
+// A piece of one line of input. Line and column numbers start at 1, and the column counts
+// characters rather than bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    pub line: usize,
+    pub column: usize,
+    pub text: &'a str,
+}
+
+// Every line of the text, numbered.
+pub fn lines(text: &str) -> impl Iterator<Item = Span<'_>> {
+    text.lines().enumerate().map(|(line_idx, line)| Span {
+        line: line_idx + 1,
+        column: 1,
+        text: line,
+    })
+}
+
+// The groups of lines separated by one or more blank lines. Blank lines at the start or end of
+// the text don't produce empty sections.
+pub fn sections(text: &str) -> impl Iterator<Item = Vec<Span<'_>>> {
+    let mut sections = Vec::new();
+    let mut current = Vec::new();
+    for line in lines(text) {
+        if line.text.trim().is_empty() {
+            if !current.is_empty() {
+                sections.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        sections.push(current);
+    }
+    sections.into_iter()
+}
+
+// The last part of a type's path, e.g. "u32" or "String", for error messages.
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+impl<'a> Span<'a> {
+    // A parse error pointing at the start of this span.
+    pub fn error(&self, message: impl Into<String>) -> AocError {
+        AocError::parse_at(self.line, self.column, message)
+    }
+
+    // The span for `piece`, which must be a slice of this span's text.
+    fn sub_span(&self, piece: &'a str) -> Span<'a> {
+        let start = piece.as_ptr() as usize - self.text.as_ptr() as usize;
+        Span {
+            line: self.line,
+            column: self.column + self.text[..start].chars().count(),
+            text: piece,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        self.sub_span(self.text.trim())
+    }
+
+    // The pieces between each `delimiter`, untrimmed.
+    pub fn split(&self, delimiter: &'a str) -> impl Iterator<Item = Span<'a>> + '_ {
+        self.text
+            .split(delimiter)
+            .map(move |piece| self.sub_span(piece))
+    }
+
+    // The whitespace-separated words.
+    pub fn fields(&self) -> impl Iterator<Item = Span<'a>> + '_ {
+        self.text
+            .split_whitespace()
+            .map(move |piece| self.sub_span(piece))
+    }
+
+    // Parse the whole span, ignoring surrounding whitespace.
+    pub fn parse<T: FromStr>(&self) -> Result<T, AocError> {
+        let trimmed = self.trim();
+        if trimmed.is_empty() {
+            return Err(trimmed.error(format!(
+                "expected a {}, found nothing",
+                short_type_name::<T>()
+            )));
+        }
+        trimmed.text.parse::<T>().map_err(|_| {
+            trimmed.error(format!(
+                "'{}' is not a valid {}",
+                trimmed.text,
+                short_type_name::<T>()
+            ))
+        })
+    }
+
+    // Every run of digits in the span, whatever is between them.
+    pub fn unsigned_ints<T: FromStr>(&self) -> Result<Vec<T>, AocError> {
+        self.numbers(false)
+    }
+
+    // Like unsigned_ints, but a '-' right before a run of digits makes it negative. That means
+    // "3-4" is [3, -4], so use unsigned_ints for ranges.
+    pub fn signed_ints<T: FromStr>(&self) -> Result<Vec<T>, AocError> {
+        self.numbers(true)
+    }
+
+    fn numbers<T: FromStr>(&self, signed: bool) -> Result<Vec<T>, AocError> {
+        let bytes = self.text.as_bytes();
+        let mut numbers = Vec::new();
+        let mut idx = 0;
+        while idx < bytes.len() {
+            if !bytes[idx].is_ascii_digit() {
+                idx += 1;
+                continue;
+            }
+            let mut start = idx;
+            if signed && start > 0 && bytes[start - 1] == b'-' {
+                start -= 1;
+            }
+            while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+                idx += 1;
+            }
+            numbers.push(self.sub_span(&self.text[start..idx]).parse()?);
+        }
+        Ok(numbers)
+    }
+
+    // Split on `delimiter` and parse each piece, e.g. "75,47,61" into a Vec<u32>.
+    pub fn split_list<T: FromStr>(&self, delimiter: &'a str) -> Result<Vec<T>, AocError> {
+        self.split(delimiter).map(|piece| piece.parse()).collect()
+    }
+
+    // Split on `delimiter` into exactly as many pieces as the tuple has fields, e.g. "47|53"
+    // into (u32, u32).
+    pub fn split_tuple<P: FromFields>(&self, delimiter: &'a str) -> Result<P, AocError> {
+        let pieces: Vec<_> = self.split(delimiter).collect();
+        if pieces.len() != P::FIELDS {
+            return Err(self.error(format!(
+                "expected {} fields separated by '{delimiter}', found {}",
+                P::FIELDS,
+                pieces.len()
+            )));
+        }
+        P::from_fields(&pieces)
+    }
+
+    // Like split_tuple, but the fields are separated by any amount of whitespace.
+    pub fn fields_tuple<P: FromFields>(&self) -> Result<P, AocError> {
+        let pieces: Vec<_> = self.fields().collect();
+        if pieces.len() != P::FIELDS {
+            return Err(self.error(format!(
+                "expected {} fields, found {}",
+                P::FIELDS,
+                pieces.len()
+            )));
+        }
+        P::from_fields(&pieces)
+    }
+
+    // Split a header like "seeds: 79 14 55 13" at the first `separator`. Both halves are trimmed,
+    // and the key can't be empty.
+    pub fn key_value(&self, separator: &str) -> Result<(Span<'a>, Span<'a>), AocError> {
+        let Some((key, value)) = self.text.split_once(separator) else {
+            return Err(self.error(format!("expected a key followed by '{separator}'")));
+        };
+        let key = self.sub_span(key).trim();
+        if key.is_empty() {
+            return Err(key.error(format!("expected a key before '{separator}'")));
+        }
+        Ok((key, self.sub_span(value).trim()))
+    }
+}
+
+// Tuples that can be parsed field by field, for split_tuple and fields_tuple.
+pub trait FromFields: Sized {
+    const FIELDS: usize;
+
+    // `fields` always has exactly FIELDS spans.
+    fn from_fields(fields: &[Span<'_>]) -> Result<Self, AocError>;
+}
+
+macro_rules! impl_from_fields {
+    ($count:literal; $($name:ident: $idx:tt),+) => {
+        impl<$($name: FromStr),+> FromFields for ($($name,)+) {
+            const FIELDS: usize = $count;
+
+            fn from_fields(fields: &[Span<'_>]) -> Result<Self, AocError> {
+                Ok(($(fields[$idx].parse::<$name>()?,)+))
+            }
+        }
+    };
+}
+
+impl_from_fields!(2; A: 0, B: 1);
+impl_from_fields!(3; A: 0, B: 1, C: 2);
+impl_from_fields!(4; A: 0, B: 1, C: 2, D: 3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(text: &str) -> Span<'_> {
+        Span {
+            line: 1,
+            column: 1,
+            text,
+        }
+    }
+
+    fn error_position<T: std::fmt::Debug>(result: Result<T, AocError>) -> (usize, Option<usize>) {
+        match result {
+            Err(AocError::Parse { line, column, .. }) => (line, column),
+            other => panic!("Expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_sections() {
+        let text = "\n47|53\n97|13\n\n\n75,47,61\n  \n1\n\n";
+        let found: Vec<_> = sections(text).collect();
+        assert_eq!(found.len(), 3);
+        assert_eq!(
+            found[0].iter().map(|line| line.text).collect::<Vec<_>>(),
+            vec!["47|53", "97|13"]
+        );
+        assert_eq!(found[1][0].line, 6);
+        assert_eq!(found[2][0].text, "1");
+        assert_eq!(sections("").count(), 0);
+    }
+
+    #[test]
+    fn test_integers() {
+        let line = span("Game 12: x=-5, y=+3 (10-20)");
+        assert_eq!(line.unsigned_ints::<u32>().unwrap(), vec![12, 5, 3, 10, 20]);
+        assert_eq!(line.signed_ints::<i64>().unwrap(), vec![12, -5, 3, 10, -20]);
+        assert!(span("no numbers").unsigned_ints::<u8>().unwrap().is_empty());
+        // 300 doesn't fit in a u8, and the error should point at it.
+        let line = Span {
+            line: 7,
+            column: 1,
+            text: "1 2 300",
+        };
+        assert_eq!(error_position(line.unsigned_ints::<u8>()), (7, Some(5)));
+    }
+
+    #[test]
+    fn test_tuples_and_lists() {
+        assert_eq!(
+            span("47|53").split_tuple::<(u32, u32)>("|").unwrap(),
+            (47, 53)
+        );
+        assert_eq!(span("3   4").fields_tuple::<(i32, i32)>().unwrap(), (3, 4));
+        assert_eq!(
+            span("a, 2, -3").split_tuple::<(char, u8, i8)>(",").unwrap(),
+            ('a', 2, -3)
+        );
+        assert_eq!(
+            span("75,47,61").split_list::<u32>(",").unwrap(),
+            vec![75, 47, 61]
+        );
+        assert_eq!(
+            error_position(span("47|53|1").split_tuple::<(u32, u32)>("|")),
+            (1, Some(1))
+        );
+        assert_eq!(
+            error_position(span("47|x3").split_tuple::<(u32, u32)>("|")),
+            (1, Some(4))
+        );
+        assert_eq!(
+            error_position(span("75,,61").split_list::<u32>(",")),
+            (1, Some(4))
+        );
+    }
+
+    #[test]
+    fn test_key_value() {
+        let (key, value) = span("Time:      7  15   30").key_value(":").unwrap();
+        assert_eq!(key.text, "Time");
+        assert_eq!((value.text, value.column), ("7  15   30", 12));
+        assert_eq!(value.unsigned_ints::<u32>().unwrap(), vec![7, 15, 30]);
+        // Columns inside the value still count from the start of the line.
+        assert_eq!(
+            error_position(value.fields_tuple::<(u8, u8, char)>()),
+            (1, Some(20))
+        );
+        assert_eq!(
+            error_position(span("seeds 79").key_value(":")),
+            (1, Some(1))
+        );
+        assert_eq!(error_position(span("  : 79").key_value(":")), (1, Some(1)));
+    }
+
+    #[test]
+    fn test_error_messages() {
+        let err = Span {
+            line: 3,
+            column: 1,
+            text: "ab",
+        }
+        .parse::<u32>()
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error on line 3, column 1: 'ab' is not a valid u32"
+        );
+        assert_eq!(
+            span("  ").parse::<u32>().unwrap_err().to_string(),
+            "Parse error on line 1, column 1: expected a u32, found nothing"
+        );
+    }
+}
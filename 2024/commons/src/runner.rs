@@ -0,0 +1,786 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crate::answers::{AnswerBook, Verdict};
+use crate::arg_parsing::{puzzle_arg_parser, ArgParser, Flag, ParsedArgs};
+use crate::input_store::{InputKind, InputStore};
+use crate::io_utilities::read_file_to_string;
+use crate::solution::{Part, RunnableSolution};
+use crate::AocError;
+
+// Maps (year, day) to the solution that handles it. A BTreeMap keeps listings in calendar order.
+#[derive(Default)]
+pub struct Registry {
+    solutions: BTreeMap<(u32, u32), Box<dyn RunnableSolution>>,
+    // Extra modes for particular days, keyed by (year, day, name), along with their help.
+    modes: BTreeMap<(u32, u32, &'static str), (&'static str, DayMode)>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Registry {
+            solutions: BTreeMap::new(),
+            modes: BTreeMap::new(),
+        }
+    }
+
+    pub fn register<S: RunnableSolution + 'static>(&mut self, year: u32, day: u32, solution: S) {
+        let previous = self.solutions.insert((year, day), Box::new(solution));
+        assert!(
+            previous.is_none(),
+            "Registered two solutions for year {year}, day {day}!"
+        );
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Option<&dyn RunnableSolution> {
+        self.solutions.get(&(year, day)).map(|s| s.as_ref())
+    }
+
+    pub fn registered_days(&self) -> Vec<(u32, u32)> {
+        self.solutions.keys().copied().collect()
+    }
+
+    // A mode for one day, as with DayBinary::mode. Runner binaries need to add the switches to
+    // their parser with `mode_switches`.
+    pub fn register_mode<F>(
+        &mut self,
+        year: u32,
+        day: u32,
+        name: &'static str,
+        help: &'static str,
+        mode: F,
+    ) where
+        F: Fn(&ParsedArgs, &str) -> Result<bool, AocError> + 'static,
+    {
+        self.insert_mode(year, day, name, help, DayMode::Text(Box::new(mode)));
+    }
+
+    // A mode that's given the input files to open, as with DayBinary::file_mode.
+    pub fn register_file_mode<F>(
+        &mut self,
+        year: u32,
+        day: u32,
+        name: &'static str,
+        help: &'static str,
+        mode: F,
+    ) where
+        F: Fn(&ParsedArgs, &[PathBuf]) -> Result<bool, AocError> + 'static,
+    {
+        self.insert_mode(year, day, name, help, DayMode::Files(Box::new(mode)));
+    }
+
+    fn insert_mode(
+        &mut self,
+        year: u32,
+        day: u32,
+        name: &'static str,
+        help: &'static str,
+        mode: DayMode,
+    ) {
+        let previous = self.modes.insert((year, day, name), (help, mode));
+        assert!(
+            previous.is_none(),
+            "Registered two --{name} modes for year {year}, day {day}!"
+        );
+    }
+
+    fn mode_names(&self) -> BTreeSet<&'static str> {
+        self.modes.keys().map(|(_, _, name)| *name).collect()
+    }
+
+    // Add a switch for every registered mode to `parser`. Modes with the same name on different
+    // days share a switch, with the help of the first one.
+    pub fn mode_switches(&self, mut parser: ArgParser) -> ArgParser {
+        let mut added = BTreeSet::new();
+        for ((_, _, name), (help, _)) in self.modes.iter() {
+            if added.insert(*name) {
+                parser = parser.flag(Flag::switch(name, help));
+            }
+        }
+        parser
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RunnerArgs {
+    pub year: u32,
+    // Only optional with --check, which then checks every day of the year.
+    pub day: Option<u32>,
+    pub part: Part,
+    pub inputs: Vec<String>,
+    pub example: Option<u32>,
+    pub input_root: Option<String>,
+    pub verbose: bool,
+    pub check: bool,
+}
+
+const DEFAULT_YEAR: &str = "2024";
+
+fn parse_part(value: &str) -> Result<Part, AocError> {
+    match value {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        "both" => Ok(Part::Both),
+        _ => Err(AocError::argument(format!(
+            "Invalid part '{value}', expected 1, 2 or both"
+        ))),
+    }
+}
+
+// The `aoc` binary's flags: the shared puzzle flags plus which puzzle to run.
+pub fn runner_arg_parser() -> ArgParser {
+    puzzle_arg_parser("aoc", "Runs any registered Advent of Code solution.")
+        .flag(Flag::value("year", "YEAR", "Puzzle year").default_value(DEFAULT_YEAR))
+        .flag(Flag::value(
+            "day",
+            "DAY",
+            "Puzzle day [required unless --check]",
+        ))
+}
+
+// Build the runner arguments from parsed flags. The year and day can be fixed by the caller, for
+// binaries that only ever run one puzzle.
+pub fn runner_args_from(
+    parsed: &ParsedArgs,
+    fixed_puzzle: Option<(u32, u32)>,
+) -> Result<RunnerArgs, AocError> {
+    let check = parsed.is_set("check");
+    let (year, day) = match fixed_puzzle {
+        Some((year, day)) => (year, Some(day)),
+        None => (
+            parsed.parsed_value::<u32>("year")?.unwrap(), // defaulted
+            parsed.parsed_value::<u32>("day")?,
+        ),
+    };
+    if day.is_none() && !check {
+        return Err(AocError::argument("Missing required flag --day"));
+    }
+    let mut inputs: Vec<String> = parsed
+        .value("input")
+        .into_iter()
+        .map(String::from)
+        .collect();
+    inputs.extend(parsed.positionals().iter().cloned());
+    let example = parsed.parsed_value::<u32>("example")?;
+    if example.is_some() && !inputs.is_empty() {
+        return Err(AocError::argument(
+            "--example can't be combined with input files",
+        ));
+    }
+    if check && (example.is_some() || !inputs.is_empty()) {
+        return Err(AocError::argument(
+            "--check runs the recorded inputs, so it can't be combined with --example or input files",
+        ));
+    }
+    Ok(RunnerArgs {
+        year,
+        day,
+        part: parse_part(parsed.value("part").unwrap())?, // defaulted
+        inputs,
+        example,
+        input_root: parsed.value("input-root").map(String::from),
+        verbose: parsed.is_set("verbose"),
+        check,
+    })
+}
+
+fn open_store(args: &RunnerArgs) -> Result<InputStore, AocError> {
+    match &args.input_root {
+        Some(root) => InputStore::new(root),
+        None => InputStore::from_env(),
+    }
+}
+
+// The files the puzzle input comes from, along with its kind when it's from the input store.
+// Input files given on the command line are used if there are any. Otherwise the input store is
+// asked for the requested example, or the full input.
+fn puzzle_input_paths(
+    args: &RunnerArgs,
+    store: &InputStore,
+    day: u32,
+) -> Result<(Vec<PathBuf>, Option<InputKind>), AocError> {
+    if args.inputs.is_empty() {
+        let kind = match args.example {
+            Some(example) => InputKind::Example(example),
+            None => InputKind::Full,
+        };
+        let path = store.resolve(args.year, day, kind)?;
+        if args.verbose {
+            println!("Reading {kind} input from {}", path.display());
+        }
+        return Ok((vec![path], Some(kind)));
+    }
+    if args.verbose {
+        for path in args.inputs.iter() {
+            println!("Reading input from {path}");
+        }
+    }
+    Ok((args.inputs.iter().map(PathBuf::from).collect(), None))
+}
+
+// Read the puzzle input, along with its kind when it came from the input store. Input files given
+// on the command line are used as-is, and several are joined as blank-line separated sections,
+// which is how multi-part puzzle inputs (like day 5's rules and updates) are laid out in a single
+// file.
+fn read_puzzle_input(
+    args: &RunnerArgs,
+    store: &InputStore,
+    day: u32,
+) -> Result<(String, Option<InputKind>), AocError> {
+    let (paths, kind) = puzzle_input_paths(args, store, day)?;
+    if kind.is_some() {
+        return Ok((read_file_to_string(&paths[0].to_string_lossy())?, kind));
+    }
+    let mut sections = Vec::new();
+    for path in paths.iter() {
+        sections.push(
+            read_file_to_string(&path.to_string_lossy())?
+                .trim_end()
+                .to_string(),
+        );
+    }
+    Ok((sections.join("\n\n"), None))
+}
+
+fn describe(answer: &Option<String>, verdict: &Verdict) -> String {
+    format!(
+        "{} ({verdict})",
+        answer.as_deref().unwrap_or("not solved yet")
+    )
+}
+
+// Run one puzzle and compare each answer against the answers file. Answers for input files given
+// on the command line are always unknown, since there's no telling which input they hold.
+// Returns false if any answer didn't match.
+pub fn run(registry: &Registry, args: &RunnerArgs) -> Result<bool, AocError> {
+    let day = args
+        .day
+        .ok_or_else(|| AocError::argument("Missing required flag --day"))?;
+    let solution = registry.get(args.year, day).ok_or_else(|| {
+        AocError::validation(format!(
+            "No solution registered for year {}, day {}. Registered days: {:?}",
+            args.year,
+            day,
+            registry.registered_days()
+        ))
+    })?;
+    let store = open_store(args)?;
+    let answers = AnswerBook::load_from_store(&store)?;
+    let (raw_input, kind) = read_puzzle_input(args, &store, day)?;
+    let start = Instant::now();
+    let mut all_matched = true;
+    for (part, answer) in solution.run(&raw_input, args.part)? {
+        let verdict = match kind {
+            Some(kind) => answers.check(args.year, day, part, kind, answer.as_deref()),
+            None => Verdict::Unknown,
+        };
+        all_matched &= !matches!(verdict, Verdict::Fail { .. });
+        println!(
+            "{} day {} part {}: {}",
+            args.year,
+            day,
+            part,
+            describe(&answer, &verdict)
+        );
+    }
+    if args.verbose {
+        println!("Solved in {:?}", start.elapsed());
+    }
+    Ok(all_matched)
+}
+
+// Run every input of the year (or just of --day) that has a recorded answer, and compare. Inputs
+// for days without a registered solution, or that aren't in the store (full inputs usually aren't
+// committed), are skipped. Returns false if any answer didn't match.
+pub fn check(registry: &Registry, args: &RunnerArgs) -> Result<bool, AocError> {
+    let store = open_store(args)?;
+    let answers = AnswerBook::load_from_store(&store)?;
+    let (mut passed, mut failed, mut unknown, mut skipped) = (0, 0, 0, 0);
+    for (year, day, kind) in answers.recorded_inputs() {
+        if year != args.year || args.day.is_some_and(|d| d != day) {
+            continue;
+        }
+        let Some(solution) = registry.get(year, day) else {
+            println!("{year} day {day} {kind}: skipped, no solution registered");
+            skipped += 1;
+            continue;
+        };
+        let raw_input = match store.read(year, day, kind) {
+            Ok(raw_input) => raw_input,
+            Err(err) => {
+                println!("{year} day {day} {kind}: skipped, {err}");
+                skipped += 1;
+                continue;
+            }
+        };
+        let start = Instant::now();
+        // A parse error fails this input, but the rest are still worth checking.
+        let results = match solution.run(&raw_input, args.part) {
+            Ok(results) => results,
+            Err(err) => {
+                println!("{year} day {day} {kind}: failed, {err}");
+                failed += 1;
+                continue;
+            }
+        };
+        for (part, answer) in results {
+            let verdict = answers.check(year, day, part, kind, answer.as_deref());
+            match verdict {
+                Verdict::Pass => passed += 1,
+                Verdict::Fail { .. } => failed += 1,
+                Verdict::Unknown => unknown += 1,
+            }
+            println!(
+                "{year} day {day} part {part} {kind}: {}",
+                describe(&answer, &verdict)
+            );
+        }
+        if args.verbose {
+            println!("Solved in {:?}", start.elapsed());
+        }
+    }
+    println!("{passed} passed, {failed} failed, {unknown} unknown, {skipped} skipped");
+    Ok(failed == 0)
+}
+
+// Run the mode chosen on the command line, if any. Otherwise check the answers or solve the
+// puzzle as usual.
+fn dispatch(registry: &Registry, parsed: &ParsedArgs, args: &RunnerArgs) -> Result<bool, AocError> {
+    let chosen: Vec<&str> = registry
+        .mode_names()
+        .into_iter()
+        .filter(|name| parsed.is_set(name))
+        .collect();
+    let name = match chosen.as_slice() {
+        [] if args.check => return check(registry, args),
+        [] => return run(registry, args),
+        [name] => *name,
+        _ => {
+            let names: Vec<String> = chosen.iter().map(|name| format!("--{name}")).collect();
+            return Err(AocError::argument(format!(
+                "Only one of {} can be used at a time",
+                names.join(", ")
+            )));
+        }
+    };
+    if args.check {
+        return Err(AocError::argument(format!(
+            "--check can't be combined with --{name}"
+        )));
+    }
+    let day = args
+        .day
+        .ok_or_else(|| AocError::argument("Missing required flag --day"))?;
+    let (_, mode) = registry.modes.get(&(args.year, day, name)).ok_or_else(|| {
+        AocError::argument(format!(
+            "--{name} isn't available for {} day {day}",
+            args.year
+        ))
+    })?;
+    let store = open_store(args)?;
+    match mode {
+        DayMode::Text(mode) => {
+            let (raw_input, _) = read_puzzle_input(args, &store, day)?;
+            mode(parsed, &raw_input)
+        }
+        DayMode::Files(mode) => {
+            let (paths, _) = puzzle_input_paths(args, &store, day)?;
+            mode(parsed, &paths)
+        }
+    }
+}
+
+fn run_with_parser<F>(parser: &ArgParser, fixed_puzzle: Option<(u32, u32)>, dispatch: F)
+where
+    F: FnOnce(&ParsedArgs, &RunnerArgs) -> Result<bool, AocError>,
+{
+    let parsed = parser.parse_env_or_exit();
+    let result = runner_args_from(&parsed, fixed_puzzle)
+        .and_then(|runner_args| dispatch(&parsed, &runner_args));
+    match result {
+        Ok(true) => {}
+        // The mismatches have already been reported.
+        Ok(false) => std::process::exit(1),
+        Err(err) => {
+            println!("{err}");
+            // The usage only helps when it's the arguments that were wrong.
+            if matches!(err, AocError::Argument { .. }) {
+                println!("{}", parser.usage());
+            }
+            std::process::exit(1);
+        }
+    }
+}
+
+// Entry point for runner binaries: parse the process arguments with `parser` (runner_arg_parser,
+// plus a `--check` switch if the binary checks answers, and the registry's mode switches),
+// dispatch to the chosen mode or the registered solution and exit with a non-zero code on any
+// error.
+pub fn run_from_env_args(parser: &ArgParser, registry: &Registry) {
+    run_with_parser(parser, None, |parsed, args| {
+        dispatch(registry, parsed, args)
+    });
+}
+
+// Entry point for the day binaries, which run a single puzzle with the shared puzzle flags.
+pub fn run_day_from_env_args<S: RunnableSolution + 'static>(year: u32, day: u32, solution: S) {
+    DayBinary::new(year, day).run(solution);
+}
+
+// Something a binary can do with a day's input other than solve the puzzle, like printing an
+// analysis of it. It sees all of the parsed flags, so it can have options of its own. Returns
+// false if it found (and already reported) a problem.
+enum DayMode {
+    // Gets the same input the puzzle would.
+    Text(TextMode),
+    // Gets the paths of the input files instead, and reads them itself. For inputs too big to
+    // read into memory.
+    Files(FileMode),
+}
+
+type TextMode = Box<dyn Fn(&ParsedArgs, &str) -> Result<bool, AocError>>;
+type FileMode = Box<dyn Fn(&ParsedArgs, &[PathBuf]) -> Result<bool, AocError>>;
+
+// A day binary with extra flags and modes on top of the shared puzzle flags:
+//
+//     DayBinary::new(2024, 5)
+//         .flag(Flag::value("update", "N", "Only draw update N"))
+//         .mode("dot", "Print the rules as a Graphviz graph", print_dot)
+//         .run(Day5);
+//
+// Each mode is chosen with a switch of the same name. Without one, the puzzle is solved as usual.
+pub struct DayBinary {
+    year: u32,
+    day: u32,
+    parser: ArgParser,
+    // Holds the modes, and the solution once `run` is called.
+    registry: Registry,
+}
+
+impl DayBinary {
+    pub fn new(year: u32, day: u32) -> Self {
+        DayBinary {
+            year,
+            day,
+            parser: puzzle_arg_parser(
+                &format!("day{day}"),
+                &format!("Solves Advent of Code {year}, day {day}."),
+            ),
+            registry: Registry::new(),
+        }
+    }
+
+    // An extra flag, usually an option for one of the modes.
+    pub fn flag(mut self, flag: Flag) -> Self {
+        self.parser = self.parser.flag(flag);
+        self
+    }
+
+    pub fn mode<F>(mut self, name: &'static str, help: &'static str, mode: F) -> Self
+    where
+        F: Fn(&ParsedArgs, &str) -> Result<bool, AocError> + 'static,
+    {
+        self.parser = self.parser.flag(Flag::switch(name, help));
+        self.registry
+            .register_mode(self.year, self.day, name, help, mode);
+        self
+    }
+
+    // A mode that's given the input files to open, rather than their contents.
+    pub fn file_mode<F>(mut self, name: &'static str, help: &'static str, mode: F) -> Self
+    where
+        F: Fn(&ParsedArgs, &[PathBuf]) -> Result<bool, AocError> + 'static,
+    {
+        self.parser = self.parser.flag(Flag::switch(name, help));
+        self.registry
+            .register_file_mode(self.year, self.day, name, help, mode);
+        self
+    }
+
+    pub fn run<S: RunnableSolution + 'static>(mut self, solution: S) {
+        self.registry.register(self.year, self.day, solution);
+        run_with_parser(&self.parser, Some((self.year, self.day)), |parsed, args| {
+            dispatch(&self.registry, parsed, args)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Solution;
+
+    struct LineCount;
+
+    impl Solution for LineCount {
+        type Input = usize;
+
+        fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError> {
+            Ok(raw_input.lines().count())
+        }
+
+        fn part_one(&self, input: &Self::Input) -> Option<String> {
+            Some(input.to_string())
+        }
+
+        fn part_two(&self, _input: &Self::Input) -> Option<String> {
+            None
+        }
+    }
+
+    // Rejects every input, like a solution whose parser has broken.
+    struct Unparseable;
+
+    impl Solution for Unparseable {
+        type Input = ();
+
+        fn parse(&self, _raw_input: &str) -> Result<Self::Input, AocError> {
+            Err(AocError::parse(1, "Not a puzzle input"))
+        }
+
+        fn part_one(&self, _input: &Self::Input) -> Option<String> {
+            None
+        }
+
+        fn part_two(&self, _input: &Self::Input) -> Option<String> {
+            None
+        }
+    }
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    // The runner's flags, with --check added the way the `aoc` binary does.
+    fn parse_runner_args(args: &[&str]) -> Result<RunnerArgs, AocError> {
+        let parser = runner_arg_parser().flag(Flag::switch("check", "Check answers"));
+        runner_args_from(&parser.parse(&to_args(args))?, None)
+    }
+
+    #[test]
+    fn test_parse_runner_args() {
+        let parsed = parse_runner_args(&[
+            "--year", "2023", "--day", "5", "--part", "2", "--input", "in",
+        ])
+        .unwrap();
+        assert_eq!(
+            parsed,
+            RunnerArgs {
+                year: 2023,
+                day: Some(5),
+                part: Part::Two,
+                inputs: vec!["in".to_string()],
+                example: None,
+                input_root: None,
+                verbose: false,
+                check: false,
+            }
+        );
+        // Defaults, and positional input files.
+        let parsed = parse_runner_args(&["--day", "5", "rules", "updates", "--verbose"]).unwrap();
+        assert_eq!(parsed.year, 2024);
+        assert_eq!(parsed.part, Part::Both);
+        assert_eq!(parsed.inputs, to_args(&["rules", "updates"]));
+        assert!(parsed.verbose);
+        let parsed = parse_runner_args(&["--day", "6", "--example"]).unwrap();
+        assert_eq!(parsed.example, Some(1));
+        assert!(parsed.inputs.is_empty());
+        // --check doesn't need a day.
+        let parsed = parse_runner_args(&["--check"]).unwrap();
+        assert!(parsed.check);
+        assert_eq!(parsed.day, None);
+    }
+
+    #[test]
+    fn test_parse_runner_args_errors() {
+        for args in [
+            vec!["input_file"],
+            vec!["--day"],
+            vec!["--day", "one", "f"],
+            vec!["--day", "1", "--part", "3", "f"],
+            vec!["--day", "1", "--bogus", "f"],
+            vec!["--day", "1", "--example", "f"],
+            vec!["--part", "1"],
+            vec!["--check", "f"],
+            vec!["--check", "--example"],
+        ] {
+            assert!(
+                matches!(parse_runner_args(&args), Err(AocError::Argument { .. })),
+                "Expected an error for {:?}",
+                args
+            );
+        }
+    }
+
+    #[test]
+    fn test_day_binary_args() {
+        let parser = puzzle_arg_parser("day1", "");
+        let parsed = parser.parse(&to_args(&["--part", "1", "input"])).unwrap();
+        let args = runner_args_from(&parsed, Some((2024, 1))).unwrap();
+        assert_eq!((args.year, args.day, args.part), (2024, Some(1), Part::One));
+        assert_eq!(args.inputs, to_args(&["input"]));
+    }
+
+    #[test]
+    fn test_day_binary_modes() {
+        let dir = std::env::temp_dir().join(format!("runner-modes-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("input.txt");
+        std::fs::write(&input, "a\nbb\n").unwrap();
+        let input = input.to_string_lossy().to_string();
+
+        let binary = DayBinary::new(2024, 1)
+            .flag(Flag::value("times", "N", "").default_value("1"))
+            .mode("shout", "", |parsed, raw| {
+                let times = parsed.parsed_value::<usize>("times")?.unwrap();
+                Ok(raw.to_uppercase().repeat(times) == "A\nBBA\nBB")
+            })
+            .mode("whisper", "", |_, _| Ok(true))
+            .file_mode("paths", "", |_, paths| Ok(paths.len() == 2));
+        let mut binary = binary;
+        binary.registry.register(2024, 1, LineCount);
+        let run_binary = |args: &[&str]| {
+            let parsed = binary.parser.parse(&to_args(args)).unwrap();
+            let runner_args = runner_args_from(&parsed, Some((2024, 1))).unwrap();
+            dispatch(&binary.registry, &parsed, &runner_args)
+        };
+
+        assert!(run_binary(&["--shout", "--times", "2", &input]).unwrap());
+        assert!(!run_binary(&["--shout", &input]).unwrap());
+        assert!(run_binary(&["--paths", &input, &input]).unwrap());
+        // Without a mode, the puzzle runs as usual.
+        assert!(run_binary(&[&input]).unwrap());
+        assert!(matches!(
+            run_binary(&["--shout", "--whisper", &input]),
+            Err(AocError::Argument { .. })
+        ));
+        // Only the `aoc` runner checks answers.
+        assert!(binary.parser.parse(&to_args(&["--check"])).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_runner_modes() {
+        let dir = std::env::temp_dir().join(format!("runner-day-modes-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("input.txt");
+        std::fs::write(&input, "a\nbb\n").unwrap();
+        let input = input.to_string_lossy().to_string();
+
+        let mut registry = Registry::new();
+        registry.register(2024, 1, LineCount);
+        registry.register_mode(2024, 1, "count", "", |_, raw| Ok(raw.lines().count() == 2));
+        registry.register_mode(2024, 2, "count", "", |_, _| Ok(false));
+        registry.register_file_mode(2024, 2, "paths", "", |_, paths| Ok(paths.len() == 1));
+        let parser = registry.mode_switches(runner_arg_parser().flag(Flag::switch("check", "")));
+        let run_runner = |args: &[&str]| {
+            let parsed = parser.parse(&to_args(args)).unwrap();
+            let runner_args = runner_args_from(&parsed, None).unwrap();
+            dispatch(&registry, &parsed, &runner_args)
+        };
+
+        // Each day gets its own mode of that name.
+        assert!(run_runner(&["--day", "1", "--count", &input]).unwrap());
+        assert!(!run_runner(&["--day", "2", "--count", &input]).unwrap());
+        assert!(run_runner(&["--day", "2", "--paths", &input]).unwrap());
+        assert!(run_runner(&["--day", "1", &input]).unwrap());
+        for args in [
+            &["--day", "1", "--paths", &input][..],
+            &["--day", "2", "--count", "--paths", &input],
+            &["--check", "--count"],
+        ] {
+            assert!(
+                matches!(run_runner(args), Err(AocError::Argument { .. })),
+                "{args:?}"
+            );
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::new();
+        registry.register(2024, 2, LineCount);
+        registry.register(2024, 1, LineCount);
+        assert_eq!(registry.registered_days(), vec![(2024, 1), (2024, 2)]);
+        assert!(registry.get(2023, 1).is_none());
+        let results = registry
+            .get(2024, 1)
+            .unwrap()
+            .run("a\nb\n", Part::Both)
+            .unwrap();
+        assert_eq!(
+            results,
+            vec![(Part::One, Some("2".to_string())), (Part::Two, None)]
+        );
+    }
+
+    #[test]
+    fn test_run_reports_missing_input() {
+        let mut registry = Registry::new();
+        registry.register(2024, 1, LineCount);
+        let args = parse_runner_args(&["--day", "1", "no/such/input"]).unwrap();
+        assert!(matches!(run(&registry, &args), Err(AocError::Io { .. })));
+        // The arguments are fine, there's just nothing registered to run them.
+        let args = parse_runner_args(&["--day", "2", "no/such/input"]).unwrap();
+        assert!(matches!(
+            run(&registry, &args),
+            Err(AocError::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_run_reads_from_input_store() {
+        let mut registry = Registry::new();
+        registry.register(2024, 6, LineCount);
+        // The example comes from the repository's input store, wherever the test is run from.
+        let args = parse_runner_args(&["--day", "6", "--example"]).unwrap();
+        assert!(run(&registry, &args).is_ok());
+        // Answers for files given on the command line are never checked.
+        let example = InputStore::from_env()
+            .unwrap()
+            .resolve(2024, 6, InputKind::Example(1))
+            .unwrap();
+        let args = parse_runner_args(&["--day", "6", example.to_string_lossy().as_ref()]).unwrap();
+        assert!(run(&registry, &args).unwrap());
+        let args = parse_runner_args(&["--day", "6", "--example=9"]).unwrap();
+        assert!(matches!(run(&registry, &args), Err(AocError::Io { .. })));
+        let args = parse_runner_args(&["--day", "6", "--input-root", "no/such/root"]).unwrap();
+        assert!(matches!(run(&registry, &args), Err(AocError::Io { .. })));
+    }
+
+    #[test]
+    fn test_answers_are_checked() {
+        // Counting lines is certainly not how day 6 is solved, so its recorded answers don't match.
+        let mut registry = Registry::new();
+        registry.register(2024, 6, LineCount);
+        let args = parse_runner_args(&["--day", "6", "--example"]).unwrap();
+        assert!(!run(&registry, &args).unwrap());
+        let args = parse_runner_args(&["--check", "--day", "6"]).unwrap();
+        assert!(!check(&registry, &args).unwrap());
+        // Days without a registered solution are skipped rather than failed.
+        let args = parse_runner_args(&["--check", "--day", "1"]).unwrap();
+        assert!(check(&registry, &args).unwrap());
+    }
+
+    #[test]
+    fn test_check_counts_parse_errors_as_failures() {
+        // Day 2's input can't be parsed, which fails the check without stopping it.
+        let mut registry = Registry::new();
+        registry.register(2024, 2, Unparseable);
+        registry.register(2024, 6, LineCount);
+        let args = parse_runner_args(&["--check"]).unwrap();
+        assert!(!check(&registry, &args).unwrap());
+        let args = parse_runner_args(&["--check", "--day", "2"]).unwrap();
+        assert!(!check(&registry, &args).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_registry_rejects_duplicates() {
+        let mut registry = Registry::new();
+        registry.register(2024, 1, LineCount);
+        registry.register(2024, 1, LineCount);
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt::Display;
+
+use crate::AocError;
+
+// Which half of a puzzle to run. Most puzzles are run with `Both`, which parses the input once
+// and then feeds it to the two parts in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    // Expand a requested part into the concrete parts to run, in order.
+    pub fn parts(&self) -> Vec<Part> {
+        match self {
+            Part::One => vec![Part::One],
+            Part::Two => vec![Part::Two],
+            Part::Both => vec![Part::One, Part::Two],
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+            Part::Both => write!(f, "both"),
+        }
+    }
+}
+
+// A puzzle solution. The raw input is parsed once into `Self::Input`, and both parts are then
+// computed from a shared reference to it. Parts that haven't been solved yet return None.
+// Malformed input is reported through the parse result rather than a panic.
+pub trait Solution {
+    type Input;
+
+    fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError>;
+    fn part_one(&self, input: &Self::Input) -> Option<String>;
+    fn part_two(&self, input: &Self::Input) -> Option<String>;
+}
+
+// Object-safe view of a `Solution`, so that solutions with different `Input` types can all be
+// stored in one registry. Implemented for every `Solution` below.
+pub trait RunnableSolution {
+    fn run(&self, raw_input: &str, part: Part) -> Result<Vec<(Part, Option<String>)>, AocError>;
+}
+
+impl<S: Solution> RunnableSolution for S {
+    fn run(&self, raw_input: &str, part: Part) -> Result<Vec<(Part, Option<String>)>, AocError> {
+        let input = self.parse(raw_input)?;
+        Ok(part
+            .parts()
+            .into_iter()
+            .map(|p| match p {
+                Part::One => (p, self.part_one(&input)),
+                Part::Two => (p, self.part_two(&input)),
+                Part::Both => unreachable!("Part::parts() never returns Both"),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct SumSolution;
+
+    impl Solution for SumSolution {
+        type Input = Vec<u32>;
+
+        fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError> {
+            raw_input
+                .split_whitespace()
+                .map(|x| {
+                    x.parse()
+                        .map_err(|_| AocError::parse(1, format!("'{x}' is not a number")))
+                })
+                .collect()
+        }
+
+        fn part_one(&self, input: &Self::Input) -> Option<String> {
+            Some(input.iter().sum::<u32>().to_string())
+        }
+
+        fn part_two(&self, _input: &Self::Input) -> Option<String> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_part_expansion() {
+        assert_eq!(Part::One.parts(), vec![Part::One]);
+        assert_eq!(Part::Two.parts(), vec![Part::Two]);
+        assert_eq!(Part::Both.parts(), vec![Part::One, Part::Two]);
+    }
+
+    #[test]
+    fn test_run_solution() {
+        let results = SumSolution.run("1 2 3", Part::Both).unwrap();
+        assert_eq!(
+            results,
+            vec![(Part::One, Some("6".to_string())), (Part::Two, None)]
+        );
+        let results = SumSolution.run("4 5", Part::One).unwrap();
+        assert_eq!(results, vec![(Part::One, Some("9".to_string()))]);
+        // Parse errors are passed through to the caller.
+        assert!(matches!(
+            SumSolution.run("4 five", Part::Both),
+            Err(AocError::Parse { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,50 @@
+// Helpers for tests in the day crates.
+
+// A small seeded xorshift generator, for tests that want lots of varied input but the same input
+// on every run. Not remotely good enough for anything else.
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    // The seed can't be zero, or every number after it would be zero too.
+    pub fn new(seed: u64) -> Self {
+        assert!(seed != 0, "XorShift needs a non-zero seed");
+        XorShift { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    // A number from 0 up to (but not including) `bound`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    // A random element of `items`.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_numbers() {
+        let mut first = XorShift::new(42);
+        let mut second = XorShift::new(42);
+        for _ in 0..100 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+        assert_ne!(XorShift::new(1).next_u64(), XorShift::new(2).next_u64());
+        let mut rng = XorShift::new(7);
+        assert!((0..100).all(|_| rng.below(3) < 3));
+        assert_eq!(*XorShift::new(7).choose(&["only"]), "only");
+    }
+}
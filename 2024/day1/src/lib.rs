@@ -0,0 +1,76 @@
+use std::iter::zip;
+
+use commons::{parse, solution::Solution, AocError};
+
+pub struct Day1;
+
+fn get_lists_from_string(input: &str) -> Result<(Vec<i32>, Vec<i32>), AocError> {
+    let mut left_vec = Vec::<i32>::new();
+    let mut right_vec = Vec::<i32>::new();
+
+    // Each non-blank line is a pair of location ids separated by whitespace.
+    for line in parse::lines(input).filter(|line| !line.text.trim().is_empty()) {
+        let (left, right) = line.fields_tuple::<(i32, i32)>()?;
+        left_vec.push(left);
+        right_vec.push(right);
+    }
+    // Sort lists and then add up the differences.
+    left_vec.sort();
+    right_vec.sort();
+    Ok((left_vec, right_vec))
+}
+
+fn sum_of_differences(left: &[i32], right: &[i32]) -> u32 {
+    zip(left, right)
+        .map(|slice| slice.0.abs_diff(*slice.1))
+        .sum()
+}
+
+impl Solution for Day1 {
+    type Input = (Vec<i32>, Vec<i32>);
+
+    fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError> {
+        get_lists_from_string(raw_input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Option<String> {
+        Some(sum_of_differences(&input.0, &input.1).to_string())
+    }
+
+    fn part_two(&self, _input: &Self::Input) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    #[test]
+    fn test_get_lists_from_string() {
+        let (left, right) = get_lists_from_string(EXAMPLE).unwrap();
+        assert_eq!(left, vec![1, 2, 3, 3, 3, 4]);
+        assert_eq!(right, vec![3, 3, 3, 4, 5, 9]);
+        assert!(matches!(
+            get_lists_from_string("1 2\n3 x\n"),
+            Err(AocError::Parse {
+                line: 2,
+                column: Some(3),
+                ..
+            })
+        ));
+        assert!(matches!(
+            get_lists_from_string("1 2\n3 4\n5\n"),
+            Err(AocError::Parse { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_example_answers() {
+        let lists = Day1.parse(EXAMPLE).unwrap();
+        assert_eq!(Day1.part_one(&lists), Some("11".to_string()));
+        assert_eq!(Day1.part_two(&lists), None);
+    }
+}
@@ -0,0 +1,66 @@
+use commons::{solution::Solution, AocError};
+
+pub mod safety;
+
+use safety::SafetyPolicy;
+
+pub struct Day2;
+
+fn get_level_vec_from_report(s: &str, line: usize) -> Result<Vec<u32>, AocError> {
+    s.split_whitespace()
+        .map(|num| {
+            num.parse::<u32>()
+                .map_err(|_| AocError::parse(line, format!("'{num}' is not a level")))
+        })
+        .collect()
+}
+
+impl Solution for Day2 {
+    type Input = Vec<Vec<u32>>;
+
+    fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError> {
+        // Blank lines would count as trivially safe reports, so skip them.
+        raw_input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| get_level_vec_from_report(line, idx + 1))
+            .collect()
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Option<String> {
+        Some(SafetyPolicy::default().count_safe(input).to_string())
+    }
+
+    // With the problem dampener, one bad level can be removed.
+    fn part_two(&self, input: &Self::Input) -> Option<String> {
+        Some(
+            SafetyPolicy::default()
+                .tolerating(1)
+                .count_safe(input)
+                .to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+
+    #[test]
+    fn test_parse_errors_report_line() {
+        assert!(matches!(
+            Day2.parse("1 2 3\n\n4 5 six\n"),
+            Err(AocError::Parse { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_example_answers() {
+        let reports = Day2.parse(EXAMPLE).unwrap();
+        assert_eq!(Day2.part_one(&reports), Some("2".to_string()));
+        assert_eq!(Day2.part_two(&reports), Some("4".to_string()));
+    }
+}
@@ -0,0 +1,224 @@
+use commons::AocError;
+
+// Which way the levels in a report have to go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Increasing,
+    Decreasing,
+    // Either way, as long as it's the same all the way through.
+    Either,
+}
+
+impl Trend {
+    pub fn parse(name: &str) -> Result<Self, AocError> {
+        match name {
+            "increasing" => Ok(Trend::Increasing),
+            "decreasing" => Ok(Trend::Decreasing),
+            "either" => Ok(Trend::Either),
+            _ => Err(AocError::argument(format!(
+                "Invalid direction '{name}', expected increasing, decreasing or either"
+            ))),
+        }
+    }
+}
+
+// What makes a report safe: every step between neighbouring levels is between `min_step` and
+// `max_step` (inclusive) in the required direction, once up to `tolerated_removals` levels have
+// been taken out. The puzzle's rules are the default, and the dampener in part two tolerates one
+// removal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafetyPolicy {
+    pub min_step: u32,
+    pub max_step: u32,
+    pub direction: Trend,
+    pub tolerated_removals: usize,
+}
+
+impl Default for SafetyPolicy {
+    fn default() -> Self {
+        SafetyPolicy {
+            min_step: 1,
+            max_step: 3,
+            direction: Trend::Either,
+            tolerated_removals: 0,
+        }
+    }
+}
+
+impl SafetyPolicy {
+    // A policy where some step could be safe, so `min_step` can't be more than `max_step`.
+    pub fn new(
+        min_step: u32,
+        max_step: u32,
+        direction: Trend,
+        tolerated_removals: usize,
+    ) -> Result<Self, AocError> {
+        if min_step > max_step {
+            return Err(AocError::validation(format!(
+                "the smallest safe step ({min_step}) is bigger than the largest ({max_step})"
+            )));
+        }
+        Ok(SafetyPolicy {
+            min_step,
+            max_step,
+            direction,
+            tolerated_removals,
+        })
+    }
+
+    pub fn tolerating(self, removals: usize) -> Self {
+        SafetyPolicy {
+            tolerated_removals: removals,
+            ..self
+        }
+    }
+
+    fn step_is_safe(&self, from: u32, to: u32, trend: Trend) -> bool {
+        let step = match trend {
+            Trend::Increasing => to.checked_sub(from),
+            _ => from.checked_sub(to),
+        };
+        step.is_some_and(|step| (self.min_step..=self.max_step).contains(&step))
+    }
+
+    pub fn is_safe(&self, levels: &[u32]) -> bool {
+        match self.direction {
+            Trend::Either => {
+                self.is_safe_going(levels, Trend::Increasing)
+                    || self.is_safe_going(levels, Trend::Decreasing)
+            }
+            trend => self.is_safe_going(levels, trend),
+        }
+    }
+
+    // Instead of trying every way of removing levels, this keeps track of which levels can end a
+    // safe run, and how many removals it took to get there. A level can only follow one of the
+    // k + 1 levels before it (anything further back means more than k removals), so it's
+    // O(n * k^2) rather than one copy of the report per removal.
+    fn is_safe_going(&self, levels: &[u32], trend: Trend) -> bool {
+        let k = self.tolerated_removals;
+        let n = levels.len();
+        // Keeping a single level (or none) is always safe.
+        if n <= k + 1 {
+            return true;
+        }
+        // reachable[i][j]: there's a safe run ending with level i, using j removals on the way.
+        let mut reachable = vec![vec![false; k + 1]; n];
+        for i in 0..n {
+            // Removing everything before level i.
+            if i <= k {
+                reachable[i][i] = true;
+            }
+            for previous in i.saturating_sub(k + 1)..i {
+                if !self.step_is_safe(levels[previous], levels[i], trend) {
+                    continue;
+                }
+                let skipped = i - previous - 1;
+                for removals in 0..=k - skipped {
+                    if reachable[previous][removals] {
+                        reachable[i][removals + skipped] = true;
+                    }
+                }
+            }
+            // Removing everything after level i finishes the report.
+            let after = n - 1 - i;
+            if after <= k && reachable[i][..=k - after].iter().any(|reached| *reached) {
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn count_safe(&self, reports: &[Vec<u32>]) -> usize {
+        reports.iter().filter(|levels| self.is_safe(levels)).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commons::test_utils::XorShift;
+
+    // Try every way of removing up to `removals` levels.
+    fn brute_force_is_safe(policy: &SafetyPolicy, levels: &[u32], removals: usize) -> bool {
+        let strict = policy.tolerating(0);
+        strict.is_safe(levels)
+            || (removals > 0
+                && (0..levels.len()).any(|idx| {
+                    let mut fewer = levels.to_vec();
+                    fewer.remove(idx);
+                    brute_force_is_safe(policy, &fewer, removals - 1)
+                }))
+    }
+
+    #[test]
+    fn test_policies() {
+        let policy = SafetyPolicy::default();
+        assert!(policy.is_safe(&[7, 6, 4, 2, 1]));
+        assert!(!policy.is_safe(&[1, 3, 2, 4, 5]));
+        assert!(policy.tolerating(1).is_safe(&[1, 3, 2, 4, 5]));
+        assert!(!policy.tolerating(1).is_safe(&[1, 2, 7, 8, 9]));
+        assert!(policy.tolerating(2).is_safe(&[1, 2, 7, 8, 9, 10]));
+        assert!(policy.is_safe(&[]) && policy.is_safe(&[5]));
+
+        let rising = SafetyPolicy {
+            direction: Trend::Increasing,
+            ..policy
+        };
+        assert!(!rising.is_safe(&[7, 6, 4, 2, 1]));
+        assert!(rising.is_safe(&[1, 3, 6, 7, 9]));
+        let gentle = SafetyPolicy {
+            max_step: 2,
+            ..policy
+        };
+        assert!(!gentle.is_safe(&[1, 3, 6, 7, 9]));
+        assert!(gentle.tolerating(1).is_safe(&[1, 3, 6, 5, 7, 9]));
+        // The first or last level can be the one that goes.
+        assert!(policy.tolerating(1).is_safe(&[9, 1, 2, 3]));
+        assert!(policy.tolerating(1).is_safe(&[1, 2, 3, 9]));
+        assert!(Trend::parse("sideways").is_err());
+        assert_eq!(
+            SafetyPolicy::new(2, 2, Trend::Either, 1).unwrap(),
+            SafetyPolicy {
+                min_step: 2,
+                max_step: 2,
+                ..policy.tolerating(1)
+            }
+        );
+        assert!(matches!(
+            SafetyPolicy::new(4, 3, Trend::Either, 0),
+            Err(AocError::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = XorShift::new(88172645463325252);
+        let policies = [
+            SafetyPolicy::default(),
+            SafetyPolicy {
+                min_step: 2,
+                max_step: 5,
+                direction: Trend::Decreasing,
+                tolerated_removals: 0,
+            },
+        ];
+        for _ in 0..2000 {
+            let len = rng.below(9) as usize;
+            let start = 20 + rng.below(10) as u32;
+            let mut levels = vec![start; len];
+            for idx in 1..len {
+                levels[idx] = (levels[idx - 1] + rng.below(9) as u32).saturating_sub(4);
+            }
+            for policy in policies {
+                for removals in 0..=3 {
+                    assert_eq!(
+                        policy.tolerating(removals).is_safe(&levels),
+                        brute_force_is_safe(&policy, &levels, removals),
+                        "{levels:?} with {removals} removals"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,260 @@
+// The corrupted memory, run as a program. A tokenizer picks out every well formed call to an
+// instruction it knows, e.g. `mul(2,4)` or `do()`, and skips everything else. The calls are then
+// run in order against a small machine, which tracks whether instructions are enabled and the
+// totals for both parts.
+
+// Arguments are 1-3 digit numbers.
+pub const MAX_ARGUMENT_DIGITS: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Machine {
+    pub enabled: bool,
+    // Part one: every result, whether or not it was enabled.
+    pub total: u64,
+    // Part two: only the results produced while enabled.
+    pub enabled_total: u64,
+}
+
+impl Default for Machine {
+    // Instructions start out enabled.
+    fn default() -> Self {
+        Machine {
+            enabled: true,
+            total: 0,
+            enabled_total: 0,
+        }
+    }
+}
+
+impl Machine {
+    pub fn new() -> Self {
+        Machine::default()
+    }
+
+    // Add the result of an operation to the totals.
+    pub fn add_result(&mut self, value: u64) {
+        self.total += value;
+        if self.enabled {
+            self.enabled_total += value;
+        }
+    }
+}
+
+pub type Operation = fn(&mut Machine, &[u32]);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Instruction {
+    pub name: &'static str,
+    pub arity: usize,
+    pub operation: Operation,
+}
+
+pub const MUL: Instruction = Instruction {
+    name: "mul",
+    arity: 2,
+    operation: |machine, args| machine.add_result(args[0] as u64 * args[1] as u64),
+};
+
+pub const DO: Instruction = Instruction {
+    name: "do",
+    arity: 0,
+    operation: |machine, _| machine.enabled = true,
+};
+
+pub const DONT: Instruction = Instruction {
+    name: "don't",
+    arity: 0,
+    operation: |machine, _| machine.enabled = false,
+};
+
+// The instructions the tokenizer looks for. New ones just need a name, an arity and an operation.
+#[derive(Debug, Clone)]
+pub struct InstructionSet {
+    instructions: Vec<Instruction>,
+}
+
+impl Default for InstructionSet {
+    // Everything the puzzle uses: mul, do and don't.
+    fn default() -> Self {
+        InstructionSet {
+            instructions: vec![MUL, DO, DONT],
+        }
+    }
+}
+
+impl InstructionSet {
+    pub fn empty() -> Self {
+        InstructionSet {
+            instructions: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    pub fn get(&self, index: usize) -> &Instruction {
+        &self.instructions[index]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Instruction> {
+        self.instructions.iter()
+    }
+
+    // The instruction whose call starts at the beginning of `text`, with its arguments and the
+    // length of the call. If several match, the first in the set wins. Works on bytes, since
+    // calls are plain ASCII and anything else is just corruption to skip over.
+    pub fn call_at(&self, text: &[u8]) -> Option<(usize, Vec<u32>, usize)> {
+        self.instructions
+            .iter()
+            .enumerate()
+            .find_map(|(index, instruction)| {
+                let (args, len) = parse_call(text, instruction)?;
+                Some((index, args, len))
+            })
+    }
+
+    // The most bytes a call can take up. Whether a call starts somewhere can always be decided
+    // from this many bytes.
+    pub fn longest_call(&self) -> usize {
+        self.instructions
+            .iter()
+            .map(|instruction| {
+                let separators = instruction.arity.saturating_sub(1);
+                instruction.name.len() + 2 + instruction.arity * MAX_ARGUMENT_DIGITS + separators
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn apply(&self, machine: &mut Machine, token: &Token) {
+        (self.get(token.instruction).operation)(machine, &token.args);
+    }
+}
+
+// `name(arg,arg,...)` at the start of `text`, with exactly the instruction's arity. Returns the
+// arguments and how many bytes the call takes up.
+fn parse_call(text: &[u8], instruction: &Instruction) -> Option<(Vec<u32>, usize)> {
+    let rest = text
+        .strip_prefix(instruction.name.as_bytes())?
+        .strip_prefix(b"(")?;
+    let mut args = Vec::with_capacity(instruction.arity);
+    let mut len = instruction.name.len() + 1;
+    let mut rest = rest;
+    for arg_idx in 0..instruction.arity {
+        if arg_idx > 0 {
+            rest = rest.strip_prefix(b",")?;
+            len += 1;
+        }
+        let digits = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+        if digits == 0 || digits > MAX_ARGUMENT_DIGITS {
+            return None;
+        }
+        args.push(
+            rest[..digits]
+                .iter()
+                .fold(0, |value, digit| value * 10 + (digit - b'0') as u32),
+        );
+        rest = &rest[digits..];
+        len += digits;
+    }
+    rest.strip_prefix(b")")?;
+    Some((args, len + 1))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    // Byte offset of the call in the memory.
+    pub offset: usize,
+    // Index into the instruction set.
+    pub instruction: usize,
+    pub args: Vec<u32>,
+}
+
+// Every call in the memory, in order. Calls don't overlap: scanning carries on after the end of
+// each call found.
+pub fn tokenize(memory: &str, instructions: &InstructionSet) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    let memory = memory.as_bytes();
+    while offset < memory.len() {
+        if let Some((instruction, args, len)) = instructions.call_at(&memory[offset..]) {
+            tokens.push(Token {
+                offset,
+                instruction,
+                args,
+            });
+            offset += len;
+        } else {
+            offset += 1;
+        }
+    }
+    tokens
+}
+
+pub fn execute(tokens: &[Token], instructions: &InstructionSet) -> Machine {
+    let mut machine = Machine::new();
+    for token in tokens {
+        instructions.apply(&mut machine, token);
+    }
+    machine
+}
+
+pub fn run(memory: &str, instructions: &InstructionSet) -> Machine {
+    execute(&tokenize(memory, instructions), instructions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_TWO: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn test_tokenize() {
+        let instructions = InstructionSet::default();
+        let tokens = tokenize(EXAMPLE_TWO, &instructions);
+        let names: Vec<&str> = tokens
+            .iter()
+            .map(|token| instructions.get(token.instruction).name)
+            .collect();
+        assert_eq!(names, vec!["mul", "don't", "mul", "mul", "do", "mul"]);
+        assert_eq!(tokens[1].offset, 20);
+        assert_eq!(tokens[5].args, vec![8, 5]);
+        assert_eq!(instructions.longest_call(), "mul(123,456)".len());
+        // Wrong arity, too many digits, spaces and missing brackets don't count.
+        let tokens = tokenize("mul(1,2,3)mul(1234,5)mul( 1,2)do(1)mul(4,5", &instructions);
+        assert!(tokens.is_empty());
+        // Neither does anything outside ASCII, but it doesn't get in the way either.
+        assert_eq!(tokenize("é mul(3,3)", &instructions)[0].offset, 3);
+    }
+
+    #[test]
+    fn test_run() {
+        let machine = run(EXAMPLE_TWO, &InstructionSet::default());
+        assert_eq!(machine.total, 161);
+        assert_eq!(machine.enabled_total, 48);
+        assert!(machine.enabled);
+        assert_eq!(Machine::default(), Machine::new());
+        assert!(Machine::default().enabled);
+    }
+
+    #[test]
+    fn test_new_instructions() {
+        const ADD: Instruction = Instruction {
+            name: "add",
+            arity: 3,
+            operation: |machine, args| machine.add_result(args.iter().map(|&a| a as u64).sum()),
+        };
+        let instructions = InstructionSet::default().with(ADD);
+        let machine = run("add(1,2,3)don't()mul(2,2)add(10,0,0)", &instructions);
+        assert_eq!(machine.total, 20);
+        assert_eq!(machine.enabled_total, 6);
+        // Without the toggles, everything counts in both parts.
+        let instructions = InstructionSet::empty().with(MUL);
+        let machine = run("don't()mul(2,2)", &instructions);
+        assert_eq!((machine.total, machine.enabled_total), (4, 4));
+    }
+}
@@ -0,0 +1,48 @@
+use commons::{solution::Solution, AocError};
+
+pub mod interpreter;
+pub mod lint;
+pub mod stream;
+
+use interpreter::{InstructionSet, Machine};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Machine;
+
+    // Running the memory gives both answers at once.
+    fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError> {
+        Ok(interpreter::run(raw_input, &InstructionSet::default()))
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Option<String> {
+        Some(input.total.to_string())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Option<String> {
+        Some(input.enabled_total.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_operands() {
+        let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+        let operands: Vec<Vec<u32>> = interpreter::tokenize(input, &InstructionSet::default())
+            .into_iter()
+            .map(|token| token.args)
+            .collect();
+        assert_eq!(
+            operands,
+            vec![vec![2, 4], vec![5, 5], vec![11, 8], vec![8, 5]]
+        );
+        assert_eq!(
+            Day3.part_one(&Day3.parse(input).unwrap()),
+            Some("161".to_string())
+        );
+    }
+}
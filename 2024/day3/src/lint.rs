@@ -0,0 +1,250 @@
+use std::fmt::Display;
+
+use crate::interpreter::{Instruction, InstructionSet, MAX_ARGUMENT_DIGITS};
+
+// Calls that almost count, and why they don't. Anything that starts with an instruction's name
+// and then an opening bracket is looked at, with the rules relaxed: any kind of bracket, spaces
+// anywhere, any number of arguments with any number of digits. If it still falls apart (say a
+// letter where an argument should be), it's just corruption, and isn't reported.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    Brackets { open: char, close: char },
+    Whitespace,
+    EmptyArgument { argument: usize },
+    TooManyDigits { argument: usize, digits: usize },
+    ArgumentCount { expected: usize, found: usize },
+    // The input ran out part way through.
+    Truncated,
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reason::Brackets { open, close } => write!(f, "uses {open}{close} instead of ()"),
+            Reason::Whitespace => write!(f, "has whitespace in it"),
+            Reason::EmptyArgument { argument } => write!(f, "argument {argument} is empty"),
+            Reason::TooManyDigits { argument, digits } => write!(
+                f,
+                "argument {argument} has {digits} digits, at most {MAX_ARGUMENT_DIGITS} are allowed"
+            ),
+            Reason::ArgumentCount { expected, found } => {
+                write!(f, "takes {expected} arguments, found {found}")
+            }
+            Reason::Truncated => write!(f, "is cut off by the end of the input"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NearMiss {
+    // Byte offset in the memory.
+    pub offset: usize,
+    pub text: String,
+    pub reasons: Vec<Reason>,
+}
+
+fn closing_bracket(open: u8) -> Option<u8> {
+    match open {
+        b'(' => Some(b')'),
+        b'[' => Some(b']'),
+        b'{' => Some(b'}'),
+        b'<' => Some(b'>'),
+        _ => None,
+    }
+}
+
+// The relaxed reading of a call to `instruction` at the start of `text`. Returns what's wrong with
+// it and how many bytes it takes up, or None if it isn't a near miss at all.
+fn near_miss_at(text: &[u8], instruction: &Instruction) -> Option<(Vec<Reason>, usize)> {
+    text.strip_prefix(instruction.name.as_bytes())?;
+    let mut position = instruction.name.len();
+    let mut whitespace = false;
+    let mut skip_whitespace = |position: &mut usize| {
+        while text.get(*position).is_some_and(u8::is_ascii_whitespace) {
+            whitespace = true;
+            *position += 1;
+        }
+    };
+
+    skip_whitespace(&mut position);
+    let open = *text.get(position)?;
+    closing_bracket(open)?;
+    position += 1;
+    // The number of digits in each argument.
+    let mut args = Vec::new();
+    let close = loop {
+        skip_whitespace(&mut position);
+        let digits = text[position..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        position += digits;
+        skip_whitespace(&mut position);
+        match text.get(position) {
+            None => return Some((vec![Reason::Truncated], position)),
+            Some(b',') => args.push(digits),
+            Some(&close) if [b')', b']', b'}', b'>'].contains(&close) => {
+                if digits > 0 || !args.is_empty() {
+                    args.push(digits);
+                }
+                break close;
+            }
+            Some(_) => return None,
+        }
+        position += 1;
+    };
+    position += 1;
+
+    let mut reasons = Vec::new();
+    if (open, close) != (b'(', b')') {
+        reasons.push(Reason::Brackets {
+            open: open as char,
+            close: close as char,
+        });
+    }
+    if whitespace {
+        reasons.push(Reason::Whitespace);
+    }
+    for (idx, digits) in args.iter().enumerate() {
+        if *digits == 0 {
+            reasons.push(Reason::EmptyArgument { argument: idx + 1 });
+        } else if *digits > MAX_ARGUMENT_DIGITS {
+            reasons.push(Reason::TooManyDigits {
+                argument: idx + 1,
+                digits: *digits,
+            });
+        }
+    }
+    if args.len() != instruction.arity {
+        reasons.push(Reason::ArgumentCount {
+            expected: instruction.arity,
+            found: args.len(),
+        });
+    }
+    (!reasons.is_empty()).then_some((reasons, position))
+}
+
+// Every near miss in the memory, in order. The memory is scanned the same way the tokenizer does
+// it, so nothing inside a real call is reported.
+pub fn near_misses(memory: &str, instructions: &InstructionSet) -> Vec<NearMiss> {
+    let memory = memory.as_bytes();
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while offset < memory.len() {
+        let text = &memory[offset..];
+        if let Some((_, _, len)) = instructions.call_at(text) {
+            offset += len;
+            continue;
+        }
+        if let Some((reasons, len)) = instructions
+            .iter()
+            .find_map(|instruction| near_miss_at(text, instruction))
+        {
+            found.push(NearMiss {
+                offset,
+                text: String::from_utf8_lossy(&text[..len]).to_string(),
+                reasons,
+            });
+        }
+        offset += 1;
+    }
+    found
+}
+
+// One line per near miss, then how many there were.
+pub fn format_near_misses(near_misses: &[NearMiss]) -> String {
+    let mut report = String::new();
+    for near_miss in near_misses {
+        let reasons: Vec<String> = near_miss.reasons.iter().map(Reason::to_string).collect();
+        report.push_str(&format!(
+            "{}: {:?} {}\n",
+            near_miss.offset,
+            near_miss.text,
+            reasons.join("; ")
+        ));
+    }
+    report.push_str(&format!("{} near misses\n", near_misses.len()));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reasons(memory: &str) -> Vec<Vec<Reason>> {
+        near_misses(memory, &InstructionSet::default())
+            .into_iter()
+            .map(|near_miss| near_miss.reasons)
+            .collect()
+    }
+
+    #[test]
+    fn test_reasons() {
+        assert_eq!(
+            reasons("mul(1234,5)"),
+            vec![vec![Reason::TooManyDigits {
+                argument: 1,
+                digits: 4
+            }]]
+        );
+        assert_eq!(reasons("mul ( 2 , 4 )"), vec![vec![Reason::Whitespace]]);
+        assert_eq!(
+            reasons("mul[3,7]"),
+            vec![vec![Reason::Brackets {
+                open: '[',
+                close: ']'
+            }]]
+        );
+        assert_eq!(reasons("xmul(4,"), vec![vec![Reason::Truncated]]);
+        assert_eq!(
+            reasons("mul(1,,2)don't(7)"),
+            vec![
+                vec![
+                    Reason::EmptyArgument { argument: 2 },
+                    Reason::ArgumentCount {
+                        expected: 2,
+                        found: 3
+                    }
+                ],
+                vec![Reason::ArgumentCount {
+                    expected: 0,
+                    found: 1
+                }]
+            ]
+        );
+        // Real calls, and plain corruption, aren't near misses.
+        assert!(reasons("mul(2,4)do()don't()mul(a,1)mul!mul(2,4!").is_empty());
+    }
+
+    #[test]
+    fn test_example() {
+        let memory = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+        let found = near_misses(memory, &InstructionSet::default());
+        assert_eq!(
+            found,
+            vec![
+                NearMiss {
+                    offset: 11,
+                    text: "mul[3,7]".to_string(),
+                    reasons: vec![Reason::Brackets {
+                        open: '[',
+                        close: ']'
+                    }]
+                },
+                NearMiss {
+                    offset: 38,
+                    text: "mul(32,64]".to_string(),
+                    reasons: vec![Reason::Brackets {
+                        open: '(',
+                        close: ']'
+                    }]
+                },
+            ]
+        );
+        assert_eq!(
+            format_near_misses(&found[..1]),
+            "11: \"mul[3,7]\" uses [] instead of ()\n1 near misses\n"
+        );
+    }
+}
@@ -1,41 +1,26 @@
-use regex::Regex;
-use std::env;
-
-fn get_file_name() -> String {
-    let args: Vec<String> = env::args().collect();
-    let arg_len = args.len();
-    if arg_len < 2 {
-        println!("Provided args didn't have a filename! args = {:?}", args);
-        quit::with_code(1);
-    }
-    args[arg_len - 1].clone()
-}
-
-fn match_mul_operands(input: String) -> Vec<(u32, u32)> {
-    let re = Regex::new(r"mul\(([0-9]{1,3}),([0-9]{1,3})\)").unwrap();
-    re.captures_iter(&input)
-        .map(|captures| {
-            let (_, [op1, op2]) = captures.extract();
-            (op1.parse::<u32>().unwrap(), op2.parse::<u32>().unwrap())
-        })
-        .collect()
-}
+use commons::runner::DayBinary;
+use day3::{interpreter::InstructionSet, lint, stream, Day3};
 
 fn main() {
-    let src_file = get_file_name();
-    println!(
-        r#"Hello from AOC 2024 Day 3, this time in astro!"
-        "Running with problem input = {}"#,
-        src_file
-    );
-
-    let input = std::fs::read_to_string(src_file).unwrap();
-    println!("Parsing operands....");
-    let parsed_operands = match_mul_operands(input);
-    // println!(
-    //     r#"Printing all the parsed operands = {:?}"#,
-    //     parsed_operands
-    // );
-    let total: u32 = parsed_operands.into_iter().map(|(x, y)| x * y).sum();
-    println!("Total sum of uncorrupted muls: {}", total);
+    DayBinary::new(2024, 3)
+        .file_mode(
+            "stream",
+            "Scan the input a chunk at a time instead of reading it all in, for huge inputs",
+            |_, paths| {
+                let machine = stream::run_files(paths, &InstructionSet::default())?;
+                println!("2024 day 3 part 1: {}", machine.total);
+                println!("2024 day 3 part 2: {}", machine.enabled_total);
+                Ok(true)
+            },
+        )
+        .mode(
+            "lint",
+            "List the almost-valid instructions, with their byte offsets and what's wrong with them",
+            |_, raw_input| {
+                let near_misses = lint::near_misses(raw_input, &InstructionSet::default());
+                print!("{}", lint::format_near_misses(&near_misses));
+                Ok(true)
+            },
+        )
+        .run(Day3);
 }
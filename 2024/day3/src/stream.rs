@@ -0,0 +1,161 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use commons::AocError;
+
+use crate::interpreter::{InstructionSet, Machine, Token};
+
+// The interpreter for memory dumps too big to read in one go. The input is scanned a chunk at a
+// time, as the reader hands it over. The last few bytes of each chunk are held back until the
+// next one arrives, since a call could start there and run on past the end of the chunk. Memory
+// use is one chunk plus the longest possible call, whatever the size of the input.
+
+// Every call in the reader's input, in order, passed to `on_token` as it's found. Offsets count
+// from the start of the reader. Finds exactly the same calls as interpreter::tokenize.
+pub fn scan_reader<R: BufRead>(
+    mut reader: R,
+    instructions: &InstructionSet,
+    mut on_token: impl FnMut(Token),
+) -> std::io::Result<()> {
+    let longest_call = instructions.longest_call();
+    // Bytes read but not scanned yet, and the offset of the first of them.
+    let mut pending: Vec<u8> = Vec::new();
+    let mut offset = 0;
+    loop {
+        let chunk = reader.fill_buf()?;
+        let at_end = chunk.is_empty();
+        pending.extend_from_slice(chunk);
+        let read = chunk.len();
+        reader.consume(read);
+
+        // Until the input runs out, stop where a call could still be cut off.
+        let mut position = 0;
+        while position < pending.len() && (at_end || pending.len() - position >= longest_call) {
+            match instructions.call_at(&pending[position..]) {
+                Some((instruction, args, len)) => {
+                    on_token(Token {
+                        offset: offset + position,
+                        instruction,
+                        args,
+                    });
+                    position += len;
+                }
+                None => position += 1,
+            }
+        }
+        pending.drain(..position);
+        offset += position;
+        if at_end {
+            return Ok(());
+        }
+    }
+}
+
+// Run each file in turn on one machine, as if they were one dump. A call can't span two files.
+pub fn run_files<P: AsRef<Path>>(
+    paths: &[P],
+    instructions: &InstructionSet,
+) -> Result<Machine, AocError> {
+    let mut machine = Machine::new();
+    for path in paths {
+        let path = path.as_ref();
+        let io_error = |source| AocError::Io {
+            path: path.display().to_string(),
+            source,
+        };
+        let file = File::open(path).map_err(io_error)?;
+        scan_reader(BufReader::new(file), instructions, |token| {
+            instructions.apply(&mut machine, &token)
+        })
+        .map_err(io_error)?;
+    }
+    Ok(machine)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::{run, tokenize};
+    use commons::test_utils::XorShift;
+
+    // Corrupted memory made mostly of bits of calls, so that plenty of calls (and near misses)
+    // end up on chunk boundaries.
+    fn random_memory(seed: u64, len: usize) -> String {
+        const PIECES: [&str; 12] = [
+            "mul(", "do()", "don't()", "1", "23", "456", "7890", ",", ")", "(", "x", "é",
+        ];
+        let mut rng = XorShift::new(seed);
+        let mut memory = String::new();
+        while memory.len() < len {
+            let piece: &&str = rng.choose(&PIECES);
+            memory.push_str(piece);
+        }
+        memory
+    }
+
+    fn streamed_tokens(memory: &str, chunk_size: usize) -> Vec<Token> {
+        let reader = BufReader::with_capacity(chunk_size, memory.as_bytes());
+        let mut tokens = Vec::new();
+        scan_reader(reader, &InstructionSet::default(), |token| {
+            tokens.push(token)
+        })
+        .unwrap();
+        tokens
+    }
+
+    #[test]
+    fn test_calls_across_chunks() {
+        let memory = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let expected = tokenize(memory, &InstructionSet::default());
+        for chunk_size in 1..=memory.len() + 1 {
+            assert_eq!(
+                streamed_tokens(memory, chunk_size),
+                expected,
+                "chunk size {chunk_size}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_matches_the_in_memory_path() {
+        let instructions = InstructionSet::default();
+        for seed in 1..=10 {
+            let memory = random_memory(seed * 7919, 5000);
+            let expected = tokenize(&memory, &instructions);
+            assert!(expected.len() > 100);
+            for chunk_size in [1, 3, 7, 12, 13, 64, 4096] {
+                assert_eq!(
+                    streamed_tokens(&memory, chunk_size),
+                    expected,
+                    "seed {seed}, chunk size {chunk_size}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_files() {
+        let dir = std::env::temp_dir().join(format!("day3-stream-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let memory = random_memory(42, 20_000);
+        let path = dir.join("memory.txt");
+        std::fs::write(&path, &memory).unwrap();
+        let instructions = InstructionSet::default();
+        assert_eq!(
+            run_files(&[&path], &instructions).unwrap(),
+            run(&memory, &instructions)
+        );
+        // A don't() at the end of one file carries over into the next.
+        let (first, second) = (dir.join("first.txt"), dir.join("second.txt"));
+        std::fs::write(&first, "mul(2,3)don't()").unwrap();
+        std::fs::write(&second, "mul(4,5)").unwrap();
+        let machine = run_files(&[&first, &second], &instructions).unwrap();
+        assert_eq!((machine.total, machine.enabled_total), (26, 6));
+        assert!(matches!(
+            run_files(&[dir.join("missing.txt")], &instructions),
+            Err(AocError::Io { .. })
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
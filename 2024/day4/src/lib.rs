@@ -0,0 +1,44 @@
+use commons::{solution::Solution, AocError};
+
+pub mod render;
+pub mod template;
+pub mod word_search;
+
+use template::{Template, X_MAS};
+use word_search::{SearchOptions, WordSearchBoard};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = WordSearchBoard;
+
+    fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError> {
+        word_search::build_board_from_string(raw_input)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Option<String> {
+        Some(
+            input
+                .find_words(&["XMAS"], SearchOptions::default())
+                .len()
+                .to_string(),
+        )
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Option<String> {
+        let x_mas = Template::parse(X_MAS).expect("X_MAS is a valid template");
+        Some(input.find_template(&x_mas, true).len().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_answers() {
+        let board = Day4.parse(&word_search::read_example_input(1)).unwrap();
+        assert_eq!(Day4.part_one(&board), Some("18".to_string()));
+        assert_eq!(Day4.part_two(&board), Some("9".to_string()));
+    }
+}
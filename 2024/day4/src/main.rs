@@ -1,38 +1,97 @@
-use commons::arg_parsing::get_file_name_or_quit;
-
-mod word_search;
+use commons::{
+    arg_parsing::Flag, io_utilities::read_file_to_string, runner::DayBinary, solution::Solution,
+};
+use day4::{
+    render::{self, MatchClass},
+    template::{self, Template},
+    word_search::{build_board_from_string, format_matches, SearchOptions},
+    Day4,
+};
 
 fn main() {
-    let fname = get_file_name_or_quit();
-    println!("Hello from AOC Day 4! Parsing puzzle: {}", fname);
-
-    let mut word_search = word_search::build_board_from_file(&fname);
-    // for every starting index in the board, evaluate all potential matches passing through
-    // this point (for both parts)
-    for idx in 0..word_search.board.len() {
-        let pot_matches =
-            word_search::generate_potential_matches_part_one(idx.try_into().unwrap(), &word_search);
-        word_search::evaluate_words(pot_matches, &mut word_search);
-
-        if word_search.board[idx] == 'A' {
-            word_search::evaluate_words_part_two(
-                word_search::generate_potential_matches_part_two(
-                    idx.try_into().unwrap(),
-                    &word_search,
-                ),
-                &mut word_search,
-            );
-        }
-    }
-
-    // The number of matches is the size of the set "matched_sequences"
-    println!(
-        "The number of matches is: {}... And for part two: {}",
-        word_search.matched_sequences_part_one.len(),
-        word_search.matched_sequences_part_two.len(),
-    );
-    // println!(
-    //     "Exact matches is: {:?}, and evaluated sequences: {:?}",
-    //     word_search.matched_sequences, word_search.tested_sequences
-    // )
+    DayBinary::new(2024, 4)
+        .flag(
+            Flag::value(
+                "words",
+                "LIST",
+                "With --search, the comma separated words to look for",
+            )
+            .default_value("XMAS"),
+        )
+        .flag(Flag::switch(
+            "fold-reversed",
+            "With --search, report a word and its reverse over the same cells only once",
+        ))
+        .flag(Flag::switch(
+            "fold-palindromes",
+            "With --search, report each palindrome only once, not once per reading",
+        ))
+        .mode(
+            "search",
+            "List every match of the given words, with where it starts and ends",
+            |parsed, raw_input| {
+                let board = build_board_from_string(raw_input)?;
+                let words: Vec<&str> = parsed
+                    .value("words")
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(str::trim)
+                    .collect();
+                let options = SearchOptions {
+                    fold_reversed: parsed.is_set("fold-reversed"),
+                    fold_palindromes: parsed.is_set("fold-palindromes"),
+                };
+                print!("{}", format_matches(&board.find_words(&words, options)));
+                Ok(true)
+            },
+        )
+        .flag(Flag::value(
+            "template",
+            "FILE",
+            "With --find-template, the template to look for [default: part two's X-MAS]",
+        ))
+        .flag(Flag::switch(
+            "all-orientations",
+            "With --find-template, also try the template rotated and mirrored",
+        ))
+        .mode(
+            "find-template",
+            "List every placement of a 2D template, with '.' as a wildcard",
+            |parsed, raw_input| {
+                let board = build_board_from_string(raw_input)?;
+                let template = match parsed.value("template") {
+                    Some(path) => Template::parse(&read_file_to_string(path)?)?,
+                    None => Template::parse(template::X_MAS)?,
+                };
+                let placements = board.find_template(&template, parsed.is_set("all-orientations"));
+                print!("{}", template::format_placements(&placements));
+                Ok(true)
+            },
+        )
+        .flag(
+            Flag::value(
+                "class",
+                "CLASS",
+                "With --render, which matches to show: all, xmas (part one), horizontal, vertical, diagonal or x-shape",
+            )
+            .default_value("all"),
+        )
+        .flag(Flag::switch(
+            "colour",
+            "With --render, paint each match in its own colour",
+        ))
+        .mode(
+            "render",
+            "Print the board with only the matched letters showing",
+            |parsed, raw_input| {
+                let classes = MatchClass::parse(parsed.value("class").unwrap_or_default())?;
+                let board = Day4.parse(raw_input)?;
+                print!(
+                    "{}",
+                    render::render_matches(&board, &classes, parsed.is_set("colour"))
+                );
+                Ok(true)
+            },
+        )
+        .run(Day4);
 }
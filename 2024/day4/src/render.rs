@@ -0,0 +1,230 @@
+use commons::{geometry::Direction, AocError};
+
+use crate::{
+    template::{Template, X_MAS},
+    word_search::{SearchOptions, WordSearchBoard},
+};
+
+// Draw the board with only the matched letters showing, like the puzzle's illustrated examples.
+
+const RESET: &str = "\x1b[0m";
+// Overlapping matches get different colours, so they can be told apart. See `match_colours`.
+const PALETTE: [&str; 6] = [
+    "\x1b[1;31m",
+    "\x1b[1;32m",
+    "\x1b[1;33m",
+    "\x1b[1;34m",
+    "\x1b[1;35m",
+    "\x1b[1;36m",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchClass {
+    Horizontal,
+    Vertical,
+    Diagonal,
+    // Part two's crossed MASes.
+    XShape,
+}
+
+impl MatchClass {
+    // Every XMAS from part one.
+    pub const PART_ONE: [MatchClass; 3] = [
+        MatchClass::Horizontal,
+        MatchClass::Vertical,
+        MatchClass::Diagonal,
+    ];
+    // Both parts' matches.
+    pub const ALL: [MatchClass; 4] = [
+        MatchClass::Horizontal,
+        MatchClass::Vertical,
+        MatchClass::Diagonal,
+        MatchClass::XShape,
+    ];
+
+    pub fn parse(name: &str) -> Result<Vec<MatchClass>, AocError> {
+        match name {
+            "all" => Ok(MatchClass::ALL.to_vec()),
+            "xmas" => Ok(MatchClass::PART_ONE.to_vec()),
+            "horizontal" => Ok(vec![MatchClass::Horizontal]),
+            "vertical" => Ok(vec![MatchClass::Vertical]),
+            "diagonal" => Ok(vec![MatchClass::Diagonal]),
+            "x-shape" => Ok(vec![MatchClass::XShape]),
+            _ => Err(AocError::argument(format!(
+                "Invalid match class '{name}', expected all, xmas, horizontal, vertical, diagonal or x-shape"
+            ))),
+        }
+    }
+}
+
+// The board indices of every match in `classes`, one list per match. Matches are sorted, so the
+// colours come out the same on every run.
+fn matched_cells(board: &WordSearchBoard, classes: &[MatchClass]) -> Vec<Vec<usize>> {
+    let mut matches: Vec<Vec<usize>> = board
+        .find_words(&["XMAS"], SearchOptions::default())
+        .into_iter()
+        .filter(|found| {
+            let class = match found.direction {
+                Direction::Left | Direction::Right => MatchClass::Horizontal,
+                Direction::Up | Direction::Down => MatchClass::Vertical,
+                _ => MatchClass::Diagonal,
+            };
+            classes.contains(&class)
+        })
+        .map(|found| {
+            board
+                .board
+                .ray(found.start, found.direction.offset())
+                .take(found.word.chars().count())
+                .map(|(position, _)| board.board.index_of(position))
+                .collect()
+        })
+        .collect();
+    if classes.contains(&MatchClass::XShape) {
+        let x_mas = Template::parse(X_MAS).expect("X_MAS is a valid template");
+        matches.extend(board.find_template(&x_mas, true).iter().map(|placement| {
+            x_mas
+                .covered_cells(placement)
+                .into_iter()
+                .map(|position| board.board.index_of(position))
+                .collect()
+        }));
+    }
+    matches.sort();
+    matches
+}
+
+// A palette index for each match. Matches are coloured greedily in order, each taking the first
+// colour that no earlier match sharing a cell with it has. Only a match that overlaps more
+// matches than there are colours can end up sharing one, and then it just cycles.
+fn match_colours(matches: &[Vec<usize>], cell_count: usize) -> Vec<usize> {
+    // The colours of the matches covering each cell so far.
+    let mut cell_colours: Vec<Vec<usize>> = vec![Vec::new(); cell_count];
+    let mut colours = Vec::with_capacity(matches.len());
+    for (match_idx, cells) in matches.iter().enumerate() {
+        let taken = |colour: &usize| cells.iter().any(|idx| cell_colours[*idx].contains(colour));
+        let colour = (0..PALETTE.len())
+            .find(|colour| !taken(colour))
+            .unwrap_or(match_idx % PALETTE.len());
+        for idx in cells {
+            cell_colours[*idx].push(colour);
+        }
+        colours.push(colour);
+    }
+    colours
+}
+
+// One line per row, with every cell that isn't part of a shown match as '.'. With `colour`, each
+// match is painted in its own colour; where matches overlap, the later one wins.
+pub fn render_matches(board: &WordSearchBoard, classes: &[MatchClass], colour: bool) -> String {
+    let matches = matched_cells(board, classes);
+    let colours = match_colours(&matches, board.board.len());
+    let mut paint: Vec<Option<&str>> = vec![None; board.board.len()];
+    for (cells, colour) in matches.iter().zip(colours) {
+        for idx in cells {
+            paint[*idx] = Some(PALETTE[colour]);
+        }
+    }
+    let mut frame = String::with_capacity(board.board.len() * 2);
+    for (position, cell) in board.board.iter() {
+        match paint[board.board.index_of(position)] {
+            None => frame.push('.'),
+            Some(code) if colour => {
+                frame.push_str(code);
+                frame.push(*cell);
+                frame.push_str(RESET);
+            }
+            Some(_) => frame.push(*cell),
+        }
+        if position.1 + 1 == board.board.width() {
+            frame.push('\n');
+        }
+    }
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::word_search::build_test_board;
+
+    #[test]
+    fn test_puzzle_illustrations() {
+        let board = build_test_board();
+        assert_eq!(
+            render_matches(&board, &MatchClass::PART_ONE, false),
+            "....XXMAS.\n\
+             .SAMXMS...\n\
+             ...S..A...\n\
+             ..A.A.MS.X\n\
+             XMASAMX.MM\n\
+             X.....XA.A\n\
+             S.S.S.S.SS\n\
+             .A.A.A.A.A\n\
+             ..M.M.M.MM\n\
+             .X.X.XMASX\n"
+        );
+        assert_eq!(
+            render_matches(&board, &[MatchClass::XShape], false),
+            ".M.S......\n\
+             ..A..MSMS.\n\
+             .M.S.MAA..\n\
+             ..A.ASMSM.\n\
+             .M.S.M....\n\
+             ..........\n\
+             S.S.S.S.S.\n\
+             .A.A.A.A..\n\
+             M.M.M.M.M.\n\
+             ..........\n"
+        );
+    }
+
+    #[test]
+    fn test_one_class_at_a_time() {
+        let board = build_test_board();
+        let count = |class| matched_cells(&board, &[class]).len();
+        assert_eq!(count(MatchClass::Horizontal), 5);
+        assert_eq!(count(MatchClass::Vertical), 3);
+        assert_eq!(count(MatchClass::Diagonal), 10);
+        assert_eq!(count(MatchClass::XShape), 9);
+        assert!(render_matches(&board, &[MatchClass::Vertical], false)
+            .starts_with("..........\n......S...\n......A...\n"));
+        assert_eq!(MatchClass::parse("all").unwrap(), MatchClass::ALL);
+        assert_eq!(MatchClass::parse("xmas").unwrap(), MatchClass::PART_ONE);
+        assert_eq!(matched_cells(&board, &MatchClass::ALL).len(), 18 + 9);
+        assert!(MatchClass::parse("sideways").is_err());
+    }
+
+    #[test]
+    fn test_colour() {
+        let board = build_test_board();
+        let coloured = render_matches(&board, &[MatchClass::Horizontal], true);
+        assert!(coloured.starts_with(&format!(".....{}X{RESET}", PALETTE[0])));
+        // Taking the escapes out leaves the plain rendering.
+        let mut plain = coloured.replace(RESET, "");
+        for code in PALETTE {
+            plain = plain.replace(code, "");
+        }
+        assert_eq!(
+            plain,
+            render_matches(&board, &[MatchClass::Horizontal], false)
+        );
+    }
+
+    #[test]
+    fn test_overlapping_matches_get_different_colours() {
+        let board = build_test_board();
+        let matches = matched_cells(&board, &MatchClass::ALL);
+        let colours = match_colours(&matches, board.board.len());
+        for (a, first) in matches.iter().enumerate() {
+            for (b, second) in matches.iter().enumerate().skip(a + 1) {
+                if first.iter().any(|idx| second.contains(idx)) {
+                    assert_ne!(colours[a], colours[b], "matches {a} and {b} overlap");
+                }
+            }
+        }
+        // Two matches sharing a cell, and a third that touches neither.
+        let colours = match_colours(&[vec![0, 1], vec![1, 2], vec![5, 6]], 7);
+        assert_eq!(colours, vec![0, 1, 0]);
+    }
+}
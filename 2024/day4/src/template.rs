@@ -0,0 +1,262 @@
+use std::fmt::Display;
+
+use commons::{grid::Grid, grid::Position, AocError};
+
+use crate::word_search::WordSearchBoard;
+
+// Small 2D shapes to look for on the board, like part two's X-MAS. A template is written as a
+// block of text, where '.' matches any cell and every other character has to be there as is.
+
+pub const WILDCARD: char = '.';
+
+// Part two's shape. With every orientation, this is all four ways of crossing two MASes.
+pub const X_MAS: &str = "M.S\n.A.\nM.S\n";
+
+// How a template was turned before it was laid on the board: mirrored left to right first, then
+// rotated clockwise by a number of quarter turns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Orientation {
+    pub mirrored: bool,
+    pub quarter_turns: usize,
+}
+
+impl Orientation {
+    pub const UPRIGHT: Orientation = Orientation {
+        mirrored: false,
+        quarter_turns: 0,
+    };
+
+    // All eight, upright first.
+    pub fn all() -> impl Iterator<Item = Orientation> {
+        [false, true].into_iter().flat_map(|mirrored| {
+            (0..4).map(move |quarter_turns| Orientation {
+                mirrored,
+                quarter_turns,
+            })
+        })
+    }
+}
+
+impl Display for Orientation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let turns = match self.quarter_turns % 4 {
+            0 => "upright",
+            1 => "turned a quarter",
+            2 => "turned half way",
+            _ => "turned three quarters",
+        };
+        if self.mirrored {
+            write!(f, "mirrored, {turns}")
+        } else {
+            write!(f, "{turns}")
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    // None for a wildcard.
+    cells: Grid<Option<char>>,
+}
+
+impl Template {
+    pub fn parse(text: &str) -> Result<Self, AocError> {
+        let cells = Grid::parse_with(text, |c| Some((c != WILDCARD).then_some(c)))?;
+        if cells.cells().iter().all(Option::is_none) {
+            return Err(AocError::validation("the template is all wildcards"));
+        }
+        Ok(Template { cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    pub fn oriented(&self, orientation: Orientation) -> Template {
+        let mut cells = self.cells.clone();
+        if orientation.mirrored {
+            cells = Grid::from_rows(
+                cells
+                    .rows()
+                    .map(|row| row.iter().rev().copied().collect())
+                    .collect(),
+            )
+            .expect("a mirrored grid has the same shape");
+        }
+        for _ in 0..orientation.quarter_turns % 4 {
+            // Turning clockwise, the bottom of each column becomes the start of a row.
+            cells = Grid::from_rows(
+                (0..cells.width())
+                    .map(|col| {
+                        let mut row: Vec<Option<char>> = cells.column(col).copied().collect();
+                        row.reverse();
+                        row
+                    })
+                    .collect(),
+            )
+            .expect("a rotated grid is still rectangular");
+        }
+        Template { cells }
+    }
+
+    // The distinct ways of turning the template. When two orientations give the same shape (as
+    // they do for anything symmetrical), only the first is kept, so no placement is counted
+    // twice.
+    pub fn orientations(&self) -> Vec<(Orientation, Template)> {
+        let mut distinct: Vec<(Orientation, Template)> = Vec::new();
+        for orientation in Orientation::all() {
+            let turned = self.oriented(orientation);
+            if !distinct.iter().any(|(_, seen)| *seen == turned) {
+                distinct.push((orientation, turned));
+            }
+        }
+        distinct
+    }
+
+    // Whether the template fits on the board with its top left corner at (row, col).
+    fn matches_at(&self, board: &Grid<char>, (row, col): Position) -> bool {
+        if row + self.height() > board.height() || col + self.width() > board.width() {
+            return false;
+        }
+        self.cells.iter().all(|((t_row, t_col), cell)| match cell {
+            None => true,
+            Some(c) => board[(row + t_row, col + t_col)] == *c,
+        })
+    }
+
+    // The board cells the template covers (wildcards aside) when it's laid down as in `placement`.
+    pub fn covered_cells(&self, placement: &Placement) -> Vec<Position> {
+        let (row, col) = placement.top_left;
+        self.oriented(placement.orientation)
+            .cells
+            .iter()
+            .filter(|(_, cell)| cell.is_some())
+            .map(|((t_row, t_col), _)| (row + t_row, col + t_col))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Placement {
+    pub top_left: Position,
+    pub orientation: Orientation,
+}
+
+impl WordSearchBoard {
+    // Every placement of the template on the board, in row-major order of the top left corner.
+    // With `all_orientations`, the template is also tried rotated and mirrored.
+    pub fn find_template(&self, template: &Template, all_orientations: bool) -> Vec<Placement> {
+        let orientations = if all_orientations {
+            template.orientations()
+        } else {
+            vec![(Orientation::UPRIGHT, template.clone())]
+        };
+        self.board
+            .positions()
+            .flat_map(|top_left| {
+                orientations
+                    .iter()
+                    .filter(move |(_, turned)| turned.matches_at(&self.board, top_left))
+                    .map(move |(orientation, _)| Placement {
+                        top_left,
+                        orientation: *orientation,
+                    })
+            })
+            .collect()
+    }
+}
+
+// One line per placement, then how many there were.
+pub fn format_placements(placements: &[Placement]) -> String {
+    let mut report = String::new();
+    for placement in placements {
+        report.push_str(&format!(
+            "{:?} {}\n",
+            placement.top_left, placement.orientation
+        ));
+    }
+    report.push_str(&format!("{} placements\n", placements.len()));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::word_search::{build_board_from_string, build_test_board};
+
+    #[test]
+    fn test_orientations() {
+        let template = Template::parse("AB.\n..C\n").unwrap();
+        let turned = |mirrored, quarter_turns| {
+            template.oriented(Orientation {
+                mirrored,
+                quarter_turns,
+            })
+        };
+        assert_eq!(turned(false, 1), Template::parse(".A\n.B\nC.\n").unwrap());
+        assert_eq!(turned(false, 2), Template::parse("C..\n.BA\n").unwrap());
+        assert_eq!(turned(true, 0), Template::parse(".BA\nC..\n").unwrap());
+        assert_eq!(turned(false, 4), template);
+        assert_eq!(template.orientations().len(), 8);
+        // X-MAS only has four different turns, and a single letter has one.
+        assert_eq!(Template::parse(X_MAS).unwrap().orientations().len(), 4);
+        assert_eq!(Template::parse("Q").unwrap().orientations().len(), 1);
+    }
+
+    #[test]
+    fn test_bad_templates() {
+        assert!(Template::parse("..\n..\n").is_err());
+        assert!(Template::parse("AB\nC\n").is_err());
+    }
+
+    #[test]
+    fn test_find_x_mas() {
+        let board = build_test_board();
+        let x_mas = Template::parse(X_MAS).unwrap();
+        assert_eq!(board.find_template(&x_mas, true).len(), 9);
+        let upright = board.find_template(&x_mas, false);
+        assert!(upright
+            .iter()
+            .all(|placement| placement.orientation == Orientation::UPRIGHT));
+        assert_eq!(upright.len(), 2);
+    }
+
+    #[test]
+    fn test_find_template() {
+        let board = build_board_from_string("ABC\nDEF\nGHI\n").unwrap();
+        let corner = Template::parse("A.\n.E\n").unwrap();
+        assert_eq!(
+            board.find_template(&corner, true),
+            vec![Placement {
+                top_left: (0, 0),
+                orientation: Orientation::UPRIGHT
+            }]
+        );
+        // Upside down, the same shape is found from E's side.
+        let corner = Template::parse("E.\n.A\n").unwrap();
+        assert!(board.find_template(&corner, false).is_empty());
+        assert_eq!(
+            board.find_template(&corner, true),
+            vec![Placement {
+                top_left: (0, 0),
+                orientation: Orientation {
+                    mirrored: false,
+                    quarter_turns: 2
+                }
+            }]
+        );
+        assert_eq!(
+            format_placements(&board.find_template(&corner, true)),
+            "(0, 0) turned half way\n1 placements\n"
+        );
+        let placement = board.find_template(&corner, true)[0];
+        assert_eq!(corner.covered_cells(&placement), vec![(0, 0), (1, 1)]);
+        // Too big to fit anywhere.
+        let wide = Template::parse("ABC.\n").unwrap();
+        assert!(board.find_template(&wide, true).is_empty());
+    }
+}
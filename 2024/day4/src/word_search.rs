@@ -1,443 +1,130 @@
 //pub mod word_search {
-use commons::io_utilities::read_file_lines;
-use std::{cmp::Ordering, collections::HashSet, iter::zip};
-
-#[derive(Hash, PartialEq, Eq, Debug, Copy, Clone)]
-pub struct IndexSequencePartOne(pub i32, pub i32, pub i32, pub i32);
-
-#[derive(Hash, PartialEq, Eq, Debug, Copy, Clone)]
-pub struct IndexSequencePartTwo(pub i32, pub i32, pub i32);
-
-#[derive(Hash, PartialEq, Eq, Debug, Copy, Clone)]
-pub struct XmasSequencePair(pub IndexSequencePartTwo, pub IndexSequencePartTwo);
-
-pub trait InBounds {
-    fn is_horizontal_in_bounds(&self, board: &WordSearchBoard) -> bool;
-    fn is_vertical_in_bounds(&self, board: &WordSearchBoard) -> bool;
-    fn is_diagonal_in_bounds(&self, board: &WordSearchBoard) -> bool;
-}
-
-pub trait MatchBoard {
-    fn evaluate_seq_for_match(&self, board: &WordSearchBoard) -> bool;
-}
-
-pub trait AsVec {
-    fn as_vec(&self) -> Vec<i32>;
-}
-
-impl AsVec for IndexSequencePartOne {
-    fn as_vec(&self) -> Vec<i32> {
-        vec![self.0, self.1, self.2, self.3]
-    }
-}
-
-impl AsVec for IndexSequencePartTwo {
-    fn as_vec(&self) -> Vec<i32> {
-        vec![self.0, self.1, self.2]
-    }
-}
-
-impl MatchBoard for IndexSequencePartOne {
-    fn evaluate_seq_for_match(&self, board: &WordSearchBoard) -> bool {
-        let v: Vec<char> = WORD_TO_MATCH.chars().collect();
-        let z = zip(self.as_vec(), v);
-        z.into_iter().all(|(seq_index, expected_char)| {
-            if board.board[seq_index as usize] == expected_char {
-                return true;
-            }
-            false
-        })
-    }
+use commons::{
+    geometry::Direction,
+    grid::{Grid, Position},
+    AocError,
+};
+use std::collections::HashSet;
+
+// The puzzle's grid of letters. Words are looked for with find_words, and 2D shapes with
+// find_template.
+pub struct WordSearchBoard {
+    pub board: Grid<char>,
 }
 
-impl MatchBoard for XmasSequencePair {
-    fn evaluate_seq_for_match(&self, board: &WordSearchBoard) -> bool {
-        let v: Vec<char> = WORD_TO_MATCH[1..].chars().collect();
-        [self.0, self.1]
-            .iter()
-            .map(|seq| {
-                let z = zip(seq.as_vec(), v.clone());
-                z.into_iter().all(|(seq_index, expected_char)| {
-                    if board.board[seq_index as usize] == expected_char {
-                        return true;
-                    }
-                    false
-                })
-            })
-            .all(|result| result)
-    }
+#[cfg(test)]
+pub fn build_test_board() -> WordSearchBoard {
+    build_board_from_string(&read_example_input(1)).unwrap()
 }
 
-impl InBounds for XmasSequencePair {
-    fn is_vertical_in_bounds(&self, board: &WordSearchBoard) -> bool {
-        let seqs_out_of_bounds: Vec<bool> = [self.0, self.1]
+// Example 1 is the puzzle's, and example 2 a 4x4 board for the search tests.
+#[cfg(test)]
+pub fn read_example_input(example: u32) -> String {
+    use commons::input_store::{InputKind, InputStore};
+    InputStore::from_env()
+        .and_then(|store| store.read(2024, 4, InputKind::Example(example)))
+        .unwrap()
+}
+
+pub fn build_board_from_string(raw_string: &str) -> Result<WordSearchBoard, AocError> {
+    Ok(WordSearchBoard {
+        board: Grid::parse(raw_string)?,
+    })
+}
+
+// A general word search, for any list of words of any length. Every match is reported with the
+// cell it starts on, the direction it reads in and the cell it ends on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WordMatch {
+    pub start: Position,
+    pub direction: Direction,
+    pub end: Position,
+    pub word: String,
+}
+
+// Reading a word backwards over the same cells as another match doesn't always count as a new
+// match. With `fold_reversed`, a word and its reverse (say XMAS and SAMX) over the same cells are
+// only reported once, as whichever comes first in the word list. With `fold_palindromes`, a
+// palindrome is only reported once for each run of cells, rather than once for each way of
+// reading it (a single letter would otherwise match in all 8 directions).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    pub fold_reversed: bool,
+    pub fold_palindromes: bool,
+}
+
+impl WordSearchBoard {
+    // Every match of every word, ordered by start cell, then direction (clockwise from up), then
+    // word. Empty words never match.
+    pub fn find_words(&self, words: &[&str], options: SearchOptions) -> Vec<WordMatch> {
+        let words: Vec<Vec<char>> = words
             .iter()
-            .map(|seq: &IndexSequencePartTwo| {
-                seq.as_vec()
-                    .into_iter()
-                    .any(|val| val < 0 || val >= board.board.len().try_into().unwrap())
-            })
+            .filter(|word| !word.is_empty())
+            .map(|word| word.chars().collect())
             .collect();
-        seqs_out_of_bounds.into_iter().all(|out_bounds| !out_bounds)
-    }
-
-    fn is_horizontal_in_bounds(&self, board: &WordSearchBoard) -> bool {
-        if self.is_vertical_in_bounds(board) {
-            let seqs_in_bounds: Vec<bool> = [self.0, self.1]
-                .iter()
-                .map(|seq: &IndexSequencePartTwo| {
-                    // Algorithm: calculate the "row number" for all the elements in the seq. If they
-                    // are all identical, the horizontal sequence doesn't wrap and it's valid.
-                    let rows: Vec<i32> = seq
-                        .as_vec()
-                        .into_iter()
-                        .map(|elem| elem / board.board_line_length)
-                        .collect();
-                    let first_row = rows.first().unwrap();
-                    if rows.iter().all(|element| element == first_row) {
-                        return true;
-                    }
-                    false
-                })
-                .collect();
-            return seqs_in_bounds.into_iter().all(|in_bounds| in_bounds);
-        }
-        false
-    }
-
-    fn is_diagonal_in_bounds(&self, board: &WordSearchBoard) -> bool {
-        // Since diagonal sequences are vertical, use the existing "vertical_in_bounds" fn.
-        if self.is_vertical_in_bounds(board) {
-            let seqs_in_bounds: Vec<bool> = [self.0, self.1]
-                .iter()
-                .map(|seq: &IndexSequencePartTwo| {
-                    // Algorithm: Calculate the "column number" for all the elements in the seq.
-                    // If the sequence is sorted, there is no wrapping around the horizontal boundary
-                    let cols: Vec<i32> = seq
-                        .as_vec()
-                        .into_iter()
-                        .map(|elem| elem % board.board_line_length)
-                        .collect();
-                    //println!("Cols = {:?}", cols);
-                    let cmp_greater_than = |a: &i32, b: &i32| {
-                        if a.cmp(b) == Ordering::Greater {
-                            return true;
-                        }
-                        false
+        let mut matches = Vec::new();
+        // The cells and (forward or backward) spelling of each match kept so far, for folding.
+        let mut kept = HashSet::new();
+        // Words go in list order, so that when two spellings fold together the first one listed
+        // is the one kept.
+        for word in words.iter() {
+            let reversed: Vec<char> = word.iter().rev().copied().collect();
+            let fold = if *word == reversed {
+                options.fold_palindromes
+            } else {
+                options.fold_reversed
+            };
+            let spelling = word.min(&reversed).clone();
+            for (start, first) in self.board.iter() {
+                if *first != word[0] {
+                    continue;
+                }
+                for direction in Direction::ALL {
+                    let Some(end) = self.match_end(start, direction, word) else {
+                        continue;
                     };
-                    if cols.is_sorted() || cols.is_sorted_by(cmp_greater_than) {
-                        //println!("Cols list is sorted...");
-                        return true;
+                    if fold && !kept.insert(((start.min(end), start.max(end)), spelling.clone())) {
+                        continue;
                     }
-                    false
-                })
-                .collect();
-            return seqs_in_bounds.into_iter().all(|in_bounds| in_bounds);
-        }
-        false
-    }
-}
-
-impl InBounds for IndexSequencePartOne {
-    fn is_vertical_in_bounds(&self, board: &WordSearchBoard) -> bool {
-        if self
-            .as_vec()
-            .into_iter()
-            .any(|val| val < 0 || val >= board.board.len().try_into().unwrap())
-        {
-            return false;
-        }
-        true
-    }
-
-    fn is_horizontal_in_bounds(&self, board: &WordSearchBoard) -> bool {
-        if self.is_vertical_in_bounds(board) {
-            // Algorithm: calculate the "row number" for all the elements in the seq. If they
-            // are all identical, the horizontal sequence doesn't wrap and it's valid.
-            let rows: Vec<i32> = self
-                .as_vec()
-                .into_iter()
-                .map(|elem| elem / board.board_line_length)
-                .collect();
-            let first_row = rows.first().unwrap();
-            if rows.iter().all(|element| element == first_row) {
-                return true;
+                    matches.push(WordMatch {
+                        start,
+                        direction,
+                        end,
+                        word: word.iter().collect(),
+                    });
+                }
             }
         }
-        false
+        matches.sort();
+        matches
     }
 
-    fn is_diagonal_in_bounds(&self, board: &WordSearchBoard) -> bool {
-        // Since diagonal sequences are vertical, use the existing "vertical_in_bounds" fn.
-        if self.is_vertical_in_bounds(board) {
-            // Algorithm: Calculate the "column number" for all the elements in the seq.
-            // If the sequence is sorted, there is no wrapping around the horizontal boundary
-            let cols: Vec<i32> = self
-                .as_vec()
-                .into_iter()
-                .map(|elem| elem % board.board_line_length)
-                .collect();
-            //println!("Cols = {:?}", cols);
-            let cmp_greater_than = |a: &i32, b: &i32| {
-                if a.cmp(b) == Ordering::Greater {
-                    return true;
-                }
-                false
-            };
-            if cols.is_sorted() || cols.is_sorted_by(cmp_greater_than) {
-                //println!("Cols list is sorted...");
-                return true;
+    // Where `word` ends if it's spelled out from `start` in `direction`, or None if it isn't.
+    fn match_end(&self, start: Position, direction: Direction, word: &[char]) -> Option<Position> {
+        let mut end = None;
+        let mut cells = self.board.ray(start, direction.offset());
+        for expected in word {
+            let (position, cell) = cells.next()?;
+            if cell != expected {
+                return None;
             }
+            end = Some(position);
         }
-        false
-    }
-}
-
-// Uncomment for part one.
-static WORD_TO_MATCH: &str = "XMAS";
-
-pub struct WordSearchBoard {
-    pub board: Vec<char>,
-    pub board_line_length: i32, // the line length of the board, so we know when rows wrap
-    // A HashMap which stores unique sequences of indices that correspond to a matching
-    // key of XMAS. For two examples, consider this board.
-    //      X M A S
-    //      M B A X
-    //      A M A S
-    //      X C B Z
-    // Starting at the board's top left, the horizontal match
-    // XMAS would be represented as (0,1,2,3).
-    // The match that starts from the bottom left and goes diagonally backwards
-    // to the top right would be represented as (12,9,6,3).
-    pub matched_sequences_part_one: HashSet<IndexSequencePartOne>,
-    pub tested_sequences_part_one: HashSet<IndexSequencePartOne>,
-
-    // Looking for 'MAS' strings in an X pattern, these variables store the sequence pairs
-    // matched and tested for part two.
-    pub matched_sequences_part_two: HashSet<XmasSequencePair>,
-    pub tested_sequences_part_two: HashSet<XmasSequencePair>,
-}
-
-pub fn build_test_board() -> WordSearchBoard {
-    let test_file = "test_input".to_string();
-    build_board_from_file(&test_file)
-}
-
-pub fn build_board_from_file(fname: &String) -> WordSearchBoard {
-    let rows = read_file_lines(fname);
-    let board_line_length = rows[0].len();
-    let mut board = Vec::new();
-    rows.iter().for_each(|row| {
-        let mut char_vec: Vec<char> = row.chars().collect();
-        board.append(&mut char_vec);
-    });
-    WordSearchBoard {
-        board,
-        board_line_length: board_line_length as i32,
-        matched_sequences_part_one: HashSet::new(),
-        tested_sequences_part_one: HashSet::new(),
-        matched_sequences_part_two: HashSet::new(),
-        tested_sequences_part_two: HashSet::new(),
+        end
     }
 }
 
-pub fn generate_part_one_sequence(base_index: i32, delta: i32) -> IndexSequencePartOne {
-    IndexSequencePartOne(
-        base_index,
-        base_index + delta,
-        base_index + 2 * delta,
-        base_index + 3 * delta,
-    )
-}
-
-pub fn generate_part_two_sequence(base_index: i32, delta: i32) -> IndexSequencePartTwo {
-    IndexSequencePartTwo(base_index, base_index + delta, base_index + 2 * delta)
-}
-
-pub fn add_new_part_one_sequence_if_untested(
-    seq: IndexSequencePartOne,
-    board: &WordSearchBoard,
-    sequences: &mut HashSet<IndexSequencePartOne>,
-) -> bool {
-    if !board.tested_sequences_part_one.contains(&seq) {
-        sequences.insert(seq);
-        return true;
-    }
-    false
-}
-
-pub fn generate_potential_matches_part_two(
-    base_index: i32,
-    board: &WordSearchBoard,
-) -> HashSet<XmasSequencePair> {
-    let mut matches = HashSet::new();
-    // Diagonal sequences always starting from the middle A
-    // M.S                          M.M    S.M    S.S
-    // .A.    extrapolated others   .A.    .A.    .A.
-    // M.S                          S.S    S.M    M.M
-
-    let line_offset = board.board_line_length;
-
-    // M.M
-    // .A.
-    // S.S
-    let value = XmasSequencePair(
-        generate_part_two_sequence(base_index - line_offset - 1, line_offset + 1),
-        generate_part_two_sequence(base_index - line_offset + 1, line_offset - 1),
-    );
-    if value.is_diagonal_in_bounds(board) && !board.tested_sequences_part_two.contains(&value) {
-        matches.insert(value);
-    }
-    // M.S
-    // .A.
-    // M.S
-    let value = XmasSequencePair(
-        generate_part_two_sequence(base_index - line_offset - 1, line_offset + 1),
-        generate_part_two_sequence(base_index + line_offset - 1, -line_offset + 1),
-    );
-    if value.is_diagonal_in_bounds(board) && !board.tested_sequences_part_two.contains(&value) {
-        matches.insert(value);
-    }
-    // S.S
-    // .A.
-    // M.M
-    let value = XmasSequencePair(
-        generate_part_two_sequence(base_index + line_offset + 1, -line_offset - 1),
-        generate_part_two_sequence(base_index + line_offset - 1, -line_offset + 1),
-    );
-    if value.is_diagonal_in_bounds(board) && !board.tested_sequences_part_two.contains(&value) {
-        matches.insert(value);
-    }
-    // S.M
-    // .A.
-    // S.M
-    let value = XmasSequencePair(
-        generate_part_two_sequence(base_index + line_offset + 1, -line_offset - 1),
-        generate_part_two_sequence(base_index - line_offset + 1, line_offset - 1),
-    );
-    if value.is_diagonal_in_bounds(board) && !board.tested_sequences_part_two.contains(&value) {
-        matches.insert(value);
+// One line per match, then how many there were.
+pub fn format_matches(matches: &[WordMatch]) -> String {
+    let mut report = String::new();
+    for found in matches {
+        report.push_str(&format!(
+            "{} from {:?} going {:?} to {:?}\n",
+            found.word, found.start, found.direction, found.end
+        ));
     }
-    matches
-}
-
-pub fn generate_potential_matches_part_one(
-    base_index: i32,
-    board: &WordSearchBoard,
-) -> HashSet<IndexSequencePartOne> {
-    // Generate a list of WordIndexes that represents all the possible
-    // matches of the WORD_TO_MATCH that could overlap with the provided 'base_index'.
-
-    let mut matches = HashSet::new();
-    let word_len = WORD_TO_MATCH.len();
-    //  - Neg offsets correspond to "forwards-reading" sequences where 'WORD_TO_MATCH' is read
-    //  normally (meaning the word starts from a previous index to 'base_index')
-    let negative_offsets = (-(word_len as i32) + 1)..=0;
-    //  - Pos offsets correspond to "backwards-reading" sequences where 'WORD_TO_MATCH' is read
-    //  in reverse character order (meaning the word starts from an index larger than 'base_index')
-    let positive_offsets = 0..word_len as i32;
-    negative_offsets.into_iter().for_each(|off| {
-        // Type 1: vertical sequences.
-        let start = base_index + (board.board_line_length * off);
-        let seq: IndexSequencePartOne = generate_part_one_sequence(start, board.board_line_length);
-        //println!("Generated seq {:?}", seq);
-        if seq.is_vertical_in_bounds(board) {
-            //println!("Added {:?}", seq);
-            add_new_part_one_sequence_if_untested(seq, board, &mut matches);
-        }
-
-        // Type 2: horizontal sequences.
-        let start = base_index + off;
-        let seq: IndexSequencePartOne = generate_part_one_sequence(start, 1);
-        //println!("Generated seq {:?}", seq);
-        if seq.is_horizontal_in_bounds(board) {
-            //println!("Added {:?}", seq);
-            add_new_part_one_sequence_if_untested(seq, board, &mut matches);
-        }
-
-        // Type 3: diagonal sequences.
-        let start = base_index + (board.board_line_length * off) + off;
-        let seq: IndexSequencePartOne =
-            generate_part_one_sequence(start, board.board_line_length + 1);
-        //println!("Generated seq {:?}", seq);
-        if seq.is_diagonal_in_bounds(board) {
-            //println!("Added {:?}", seq);
-            add_new_part_one_sequence_if_untested(seq, board, &mut matches);
-        }
-        let start = base_index + (board.board_line_length * off) - off;
-        let seq: IndexSequencePartOne =
-            generate_part_one_sequence(start, board.board_line_length - 1);
-        //println!("Generated seq {:?}", seq);
-        if seq.is_diagonal_in_bounds(board) {
-            //println!("Added {:?}", seq);
-            add_new_part_one_sequence_if_untested(seq, board, &mut matches);
-        }
-    });
-
-    positive_offsets.into_iter().for_each(|off| {
-        // Type 1: vertical sequences.
-        let start = base_index + (board.board_line_length * off);
-        let seq: IndexSequencePartOne = generate_part_one_sequence(start, -board.board_line_length);
-        //println!("Generated seq {:?}", seq);
-        if seq.is_vertical_in_bounds(board) {
-            //println!("Added {:?}", seq);
-            add_new_part_one_sequence_if_untested(seq, board, &mut matches);
-        }
-
-        // Type 2: horizontal sequences.
-        let start = base_index + off;
-        let seq: IndexSequencePartOne = generate_part_one_sequence(start, -1);
-        //println!("Generated seq {:?}", seq);
-        if seq.is_horizontal_in_bounds(board) {
-            //println!("Added {:?}", seq);
-            add_new_part_one_sequence_if_untested(seq, board, &mut matches);
-        }
-
-        // Type 3: diagonal sequences.
-        let start = base_index + (board.board_line_length * off) + off;
-        let seq: IndexSequencePartOne =
-            generate_part_one_sequence(start, -board.board_line_length - 1);
-        //println!("Generated seq {:?}", seq);
-        if seq.is_diagonal_in_bounds(board) {
-            //println!("Added {:?}", seq);
-            add_new_part_one_sequence_if_untested(seq, board, &mut matches);
-        }
-        let start = base_index + (board.board_line_length * off) - off;
-        let seq: IndexSequencePartOne =
-            generate_part_one_sequence(start, -board.board_line_length + 1);
-        //println!("Generated seq {:?}", seq);
-        if seq.is_diagonal_in_bounds(board) {
-            //println!("Added {:?}", seq);
-            add_new_part_one_sequence_if_untested(seq, board, &mut matches);
-        }
-    });
-    matches
-}
-
-pub fn evaluate_words(
-    potential_matches: HashSet<IndexSequencePartOne>,
-    board: &mut WordSearchBoard,
-) {
-    potential_matches.iter().for_each(|seq| {
-        if seq.evaluate_seq_for_match(board) {
-            // found an XMAS
-            board.matched_sequences_part_one.insert(*seq);
-        }
-        board.tested_sequences_part_one.insert(*seq);
-    });
-}
-
-pub fn evaluate_words_part_two(
-    potential_matches: HashSet<XmasSequencePair>,
-    board: &mut WordSearchBoard,
-) {
-    potential_matches.iter().for_each(|seq| {
-        if seq.evaluate_seq_for_match(board) {
-            // found an XMAS
-            board.matched_sequences_part_two.insert(*seq);
-        }
-        board.tested_sequences_part_two.insert(*seq);
-    });
+    report.push_str(&format!("{} matches\n", matches.len()));
+    report
 }
 
 #[cfg(test)]
@@ -445,299 +132,78 @@ mod tests {
     use super::*; // bring in all the functionality in the word_search module
 
     #[test]
-    fn test_generate_sequence() {
-        let base = 4;
-        let test_positive_delta = 2;
-        assert_eq!(
-            generate_part_one_sequence(base, test_positive_delta),
-            IndexSequencePartOne(4, 6, 8, 10)
-        );
-        let test_negative_delta = -3;
-        assert_eq!(
-            generate_part_one_sequence(base, test_negative_delta),
-            IndexSequencePartOne(4, 1, -2, -5)
-        );
-    }
-
-    #[test]
-    fn test_add_new_sequence_if_untested() {
-        let mut board = build_test_board();
-        let new_seq = IndexSequencePartOne(1, 2, 3, 4);
-        let mut sequences_to_test: HashSet<IndexSequencePartOne> = HashSet::new();
-        // assert new sequence is added to sequences_to_test
-        assert!(add_new_part_one_sequence_if_untested(
-            new_seq,
-            &board,
-            &mut sequences_to_test
-        ));
-        // assert vec has the new_seq value
-        assert!(sequences_to_test.contains(&new_seq));
-
-        board.tested_sequences_part_one.insert(new_seq);
-        // assert trying to add the same sequence now is false
-        assert!(!add_new_part_one_sequence_if_untested(
-            new_seq,
-            &board,
-            &mut sequences_to_test
-        ));
-    }
-
-    #[test]
-    fn test_is_vertical_sequence_in_bound() {
-        let board = build_test_board();
-        // test that a vertical sequence going off the board negatively is rejected
-        assert!(!IndexSequencePartOne(-10, 0, 11, 21).is_vertical_in_bounds(&board));
-        // test that a vertical sequence going off the board positively is rejected
-        assert!(!generate_part_one_sequence(80, 10).is_vertical_in_bounds(&board));
-        // test a vertical sequence starting at each first element is accepted.
-        for start_index in 0..board.board_line_length {
-            let seq = generate_part_one_sequence(start_index, board.board_line_length);
-            assert!(seq.is_vertical_in_bounds(&board));
-        }
+    fn test_build_board_rejects_ragged_rows() {
+        assert!(build_board_from_string("XMAS\nMAS\n").is_err());
+        assert!(build_board_from_string("").is_err());
+        let board = build_board_from_string("XMAS\nSAMX\n").unwrap();
+        assert_eq!(board.board.width(), 4);
+        assert_eq!(board.board.len(), 8);
     }
 
     #[test]
-    fn test_is_horizontal_sequence_in_bound() {
+    fn test_find_words() {
         let board = build_test_board();
-        // test that a horizontal sequence wrapping around the first column is rejected
-        assert!(!generate_part_one_sequence(board.board_line_length + 1, -1)
-            .is_horizontal_in_bounds(&board));
-        // test that a horizontal sequence starting in the middle of a line is accepted
-        assert!(generate_part_one_sequence(5, 1).is_horizontal_in_bounds(&board));
-    }
-
-    #[test]
-    fn test_is_diagonal_sequence_in_bound() {
-        let board = build_test_board();
-        // test that a diagonal sequence starting from the first element column is accepted
-        assert!(generate_part_one_sequence(0, board.board_line_length + 1)
-            .is_diagonal_in_bounds(&board));
-        // test that a diagonal sequence starting from the 3rd last element in the first row is
-        // rejected because it would wrap around columns
-        assert!(!generate_part_one_sequence(
-            board.board_line_length - 3,
-            board.board_line_length + 1
-        )
-        .is_diagonal_in_bounds(&board));
-        // test that a backwards diagonal sequence starting from the 3rd element in the last row is
-        // rejected because it would wrap around columns
-        let seq = generate_part_one_sequence(
-            board.board.len() as i32 - board.board_line_length + 2,
-            -board.board_line_length - 1,
-        );
-        //println!("Seq: {:?}", seq);
-        assert!(!seq.is_diagonal_in_bounds(&board));
-        // test that a backwards diagonal sequence starting from the 2nd last element in the last row is
-        // accepted because it is always in bounds
-        let seq =
-            generate_part_one_sequence(board.board.len() as i32 - 3, -board.board_line_length - 1);
-        //println!("Seq: {:?}", seq);
-        assert!(seq.is_diagonal_in_bounds(&board));
-        // test that a backwards diagonal sequence starting from the middle of the 2nd row is rejected because it
-        // would exit the array in negative bounds.
-        assert!(!generate_part_one_sequence(
-            board.board_line_length + 5,
-            -board.board_line_length - 1
-        )
-        .is_diagonal_in_bounds(&board));
-        // test that a diagonal sequence starting from the middle of the last row is rejected because it
-        // would exit the array in positive bounds.
-        assert!(!generate_part_one_sequence(
-            board.board.len() as i32 - (board.board_line_length * 3) + 5,
-            board.board_line_length + 1
-        )
-        .is_diagonal_in_bounds(&board));
-    }
-
-    #[test]
-    fn test_generate_potential_matches() {
-        let test_file = "mini_input".to_string();
-        let board = build_board_from_file(&test_file);
-
-        let potential_matches = generate_potential_matches_part_one(0, &board);
-        assert_eq!(
-            potential_matches,
-            HashSet::from([
-                IndexSequencePartOne(3, 2, 1, 0),
-                IndexSequencePartOne(0, 1, 2, 3),
-                IndexSequencePartOne(0, 4, 8, 12),
-                IndexSequencePartOne(0, 5, 10, 15),
-                IndexSequencePartOne(12, 8, 4, 0),
-                IndexSequencePartOne(15, 10, 5, 0),
-            ])
-        );
-        let potential_matches = generate_potential_matches_part_one(12, &board);
+        let xmas = board.find_words(&["XMAS"], SearchOptions::default());
+        assert_eq!(xmas.len(), 18);
+        // Looking for both spellings finds every placement twice, unless they're folded together.
         assert_eq!(
-            potential_matches,
-            HashSet::from([
-                IndexSequencePartOne(12, 8, 4, 0),
-                IndexSequencePartOne(12, 13, 14, 15),
-                IndexSequencePartOne(0, 4, 8, 12),
-                IndexSequencePartOne(15, 14, 13, 12),
-                IndexSequencePartOne(12, 9, 6, 3),
-                IndexSequencePartOne(3, 6, 9, 12),
-            ])
+            board
+                .find_words(&["XMAS", "SAMX"], SearchOptions::default())
+                .len(),
+            36
         );
-    }
-
-    #[test]
-    fn test_evaluate_seq_for_match() {
-        let board = build_board_from_file(&"mini_input".to_string());
-        assert!(IndexSequencePartOne(0, 1, 2, 3).evaluate_seq_for_match(&board));
-        assert!(IndexSequencePartOne(12, 9, 6, 3).evaluate_seq_for_match(&board));
-        assert!(IndexSequencePartOne(0, 4, 8, 12).evaluate_seq_for_match(&board));
-    }
+        let folded = SearchOptions {
+            fold_reversed: true,
+            ..SearchOptions::default()
+        };
+        assert_eq!(board.find_words(&["XMAS", "SAMX"], folded), xmas);
 
-    #[test]
-    fn test_evaluate_words() {
-        let mut board = build_board_from_file(&"mini_input".to_string());
-        let potential_matches = HashSet::from([
-            IndexSequencePartOne(0, 1, 2, 3),
-            IndexSequencePartOne(12, 9, 6, 3),
-            IndexSequencePartOne(0, 4, 8, 12),
-        ]);
-        evaluate_words(potential_matches, &mut board);
+        let board = build_board_from_string(&read_example_input(2)).unwrap();
         assert_eq!(
-            board.matched_sequences_part_one,
-            HashSet::from([
-                IndexSequencePartOne(0, 1, 2, 3),
-                IndexSequencePartOne(12, 9, 6, 3),
-            ]),
+            board.find_words(&["XMAS", "", "AMAS"], SearchOptions::default()),
+            vec![
+                WordMatch {
+                    start: (0, 0),
+                    direction: Direction::Right,
+                    end: (0, 3),
+                    word: "XMAS".to_string()
+                },
+                WordMatch {
+                    start: (2, 0),
+                    direction: Direction::Right,
+                    end: (2, 3),
+                    word: "AMAS".to_string()
+                },
+                WordMatch {
+                    start: (3, 0),
+                    direction: Direction::UpRight,
+                    end: (0, 3),
+                    word: "XMAS".to_string()
+                },
+            ]
         );
-        assert_eq!(
-            board.tested_sequences_part_one,
-            HashSet::from([
-                IndexSequencePartOne(0, 1, 2, 3),
-                IndexSequencePartOne(12, 9, 6, 3),
-                IndexSequencePartOne(0, 4, 8, 12)
-            ]),
+        assert!(
+            format_matches(&board.find_words(&["AMAS"], SearchOptions::default()))
+                .starts_with("AMAS from (2, 0) going Right to (2, 3)\n1 matches\n")
         );
     }
 
     #[test]
-    fn test_is_vertical_sequence_in_bound_part_two() {
-        let board = build_test_board();
-        // test that in a sequence pair, either vertical sequence going off the
-        // array bounds is rejected
-        assert!(!XmasSequencePair(
-            generate_part_two_sequence(80, 10),
-            generate_part_two_sequence(-10, 5)
-        )
-        .is_vertical_in_bounds(&board));
-        assert!(!XmasSequencePair(
-            generate_part_two_sequence(8, -5),
-            generate_part_two_sequence(10, board.board_line_length)
-        )
-        .is_vertical_in_bounds(&board));
-        // test a vertical sequence starting at each first element is accepted.
-        for start_index in 0..board.board_line_length {
-            assert!(XmasSequencePair(
-                generate_part_two_sequence(start_index, board.board_line_length),
-                generate_part_two_sequence(start_index + 1, board.board_line_length)
-            )
-            .is_vertical_in_bounds(&board));
-        }
-    }
-
-    #[test]
-    fn test_is_diagonal_sequence_in_bound_part_two() {
-        // Test that if either sequence in the XmasSequencePair goes out of vertical
-        // bounds, then the sequence is rejected.
-        let board = build_test_board();
-        assert!(!XmasSequencePair(
-            generate_part_two_sequence(5, -board.board_line_length - 1), // offending
-            generate_part_two_sequence(0, board.board_line_length + 1),
-        )
-        .is_diagonal_in_bounds(&board));
-        assert!(!XmasSequencePair(
-            generate_part_two_sequence(0, board.board_line_length + 1),
-            generate_part_two_sequence(90, board.board_line_length + 1), // offending
-        )
-        .is_diagonal_in_bounds(&board));
-
-        // Test that if either sequence in the XmasSequencePair wraps around the
-        // horizontal bounds of one column, the sequene is rejected.
-        assert!(!XmasSequencePair(
-            generate_part_two_sequence(0, 1),
-            generate_part_two_sequence(board.board_line_length - 1, 1), // offending
-        )
-        .is_diagonal_in_bounds(&board));
-        assert!(!XmasSequencePair(
-            generate_part_two_sequence(board.board_line_length, -1), // offending
-            generate_part_two_sequence(board.board_line_length, 1),
-        )
-        .is_diagonal_in_bounds(&board));
-        assert!(!XmasSequencePair(
-            generate_part_two_sequence(board.board_line_length + 1, -1), // offending
-            generate_part_two_sequence(board.board_line_length, 1),
-        )
-        .is_diagonal_in_bounds(&board));
-        assert!(!XmasSequencePair(
-            generate_part_two_sequence(2 * board.board_line_length - 2, 1), // offending
-            generate_part_two_sequence(board.board_line_length, 1),
-        )
-        .is_diagonal_in_bounds(&board));
-    }
-
-    #[test]
-    fn test_generate_potential_matches_part_two() {
-        let test_file = "mini_input".to_string();
-        let board = build_board_from_file(&test_file);
-
-        // Testing from any input on the first row (i.e., index 2), will mean
-        // that no matches should be returned, they are all out of bounds.
-        let potential_matches = generate_potential_matches_part_two(2, &board);
-        assert_eq!(potential_matches, HashSet::from([]));
-
-        // Testing from an input in the second row and first/last columns should also
-        // return zero matches because of bounds.
-        let potential_matches =
-            generate_potential_matches_part_two(board.board_line_length, &board);
-        assert_eq!(potential_matches, HashSet::from([]));
-        let potential_matches =
-            generate_potential_matches_part_two(2 * board.board_line_length - 1, &board);
-        assert_eq!(potential_matches, HashSet::from([]));
-        // Testing from an input in the second row and a central column should
-        // return a valid potential_match
-        let potential_matches =
-            generate_potential_matches_part_two(board.board_line_length + 2, &board);
-        assert_eq!(
-            potential_matches,
-            HashSet::from([
-                // M.S
-                // .A.
-                // M.S
-                XmasSequencePair(
-                    IndexSequencePartTwo(1, 6, 11),
-                    IndexSequencePartTwo(9, 6, 3),
-                ),
-                // M.M
-                // .A.
-                // S.S
-                XmasSequencePair(
-                    IndexSequencePartTwo(1, 6, 11),
-                    IndexSequencePartTwo(3, 6, 9),
-                ),
-                // S.S
-                // .A.
-                // M.M
-                XmasSequencePair(
-                    IndexSequencePartTwo(11, 6, 1),
-                    IndexSequencePartTwo(9, 6, 3),
-                ),
-                // S.M
-                // .A.
-                // S.M
-                XmasSequencePair(
-                    IndexSequencePartTwo(11, 6, 1),
-                    IndexSequencePartTwo(3, 6, 9),
-                ),
-            ])
-        );
-
-        // Testing from an input in the last row shoudl also return nothing.
-        let potential_matches =
-            generate_potential_matches_part_two((board.board.len() - 1) as i32, &board);
-        assert_eq!(potential_matches, HashSet::from([]));
+    fn test_find_palindromes() {
+        let board = build_board_from_string(&read_example_input(2)).unwrap();
+        // AMA reads the same both ways, and so does every single letter.
+        let options = SearchOptions::default();
+        assert_eq!(board.find_words(&["AMA"], options).len(), 2);
+        assert_eq!(board.find_words(&["Z"], options).len(), 8);
+        let folded = SearchOptions {
+            fold_palindromes: true,
+            ..SearchOptions::default()
+        };
+        let amas = board.find_words(&["AMA"], folded);
+        assert_eq!(amas.len(), 1);
+        assert_eq!((amas[0].start, amas[0].end), ((2, 0), (2, 2)));
+        assert_eq!(board.find_words(&["Z"], folded).len(), 1);
+        // Folding palindromes leaves other words alone.
+        assert_eq!(board.find_words(&["XMAS", "SAMX"], folded).len(), 4);
     }
 }
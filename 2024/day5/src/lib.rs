@@ -0,0 +1,455 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+    fmt::Display,
+};
+
+use commons::{
+    io_utilities::read_file_to_string,
+    parse::{self, Span},
+    solution::Solution,
+    AocError,
+};
+
+pub mod rule_graph;
+
+pub struct Day5;
+
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct PageOrdering {
+    first: u32,
+    second: u32,
+}
+
+// The rules and updates found in one file. The official input has both, separated by a blank
+// line, but the rules and updates can also come from separate files.
+#[derive(Default)]
+struct PuzzleSections {
+    rules: Option<HashSet<PageOrdering>>,
+    updates: Option<Vec<Vec<u32>>>,
+}
+
+// Work out what each blank-line separated section holds from its first line: rules look like
+// "47|53", and anything else should be a comma separated update.
+fn parse_sections(text: &str) -> Result<PuzzleSections, AocError> {
+    let mut found = PuzzleSections::default();
+    for section in parse::sections(text) {
+        if section[0].text.contains('|') {
+            if found.rules.is_some() {
+                return Err(section[0].error("found a second block of ordering rules"));
+            }
+            found.rules = Some(build_ruleset_from_lines(&section)?);
+        } else {
+            if found.updates.is_some() {
+                return Err(section[0].error("found a second block of page updates"));
+            }
+            found.updates = Some(build_page_updates_from_lines(&section)?);
+        }
+    }
+    Ok(found)
+}
+
+// Read the ordering rules from either a rules file or a combined puzzle input.
+pub fn build_ruleset(rule_file: &str) -> Result<HashSet<PageOrdering>, AocError> {
+    parse_sections(&read_file_to_string(rule_file)?)?
+        .rules
+        .ok_or_else(|| AocError::validation(format!("'{rule_file}' has no page ordering rules")))
+}
+
+fn build_ruleset_from_lines(rules: &[Span]) -> Result<HashSet<PageOrdering>, AocError> {
+    let mut ret_set = HashSet::new();
+    for rule in rules {
+        let (first, second) = rule.split_tuple::<(u32, u32)>("|")?;
+        ret_set.insert(PageOrdering { first, second });
+    }
+    Ok(ret_set)
+}
+
+// Read the page updates from either an updates file or a combined puzzle input.
+pub fn build_page_updates(update_file: &str) -> Result<Vec<Vec<u32>>, AocError> {
+    parse_sections(&read_file_to_string(update_file)?)?
+        .updates
+        .ok_or_else(|| AocError::validation(format!("'{update_file}' has no page updates")))
+}
+
+// Every update needs a middle page, so it has to have an odd number of pages.
+fn build_page_updates_from_lines(lines: &[Span]) -> Result<Vec<Vec<u32>>, AocError> {
+    lines
+        .iter()
+        .map(|line| {
+            let pages: Vec<u32> = line.split_list(",")?;
+            if pages.len().is_multiple_of(2) {
+                return Err(line.error(format!(
+                    "an update needs an odd number of pages to have a middle one, found {}",
+                    pages.len()
+                )));
+            }
+            Ok(pages)
+        })
+        .collect()
+}
+
+fn build_reversed_orderings(page_updates: &[u32]) -> Vec<PageOrdering> {
+    let mut v = Vec::new();
+    for i in 0..page_updates.len() {
+        for j in i + 1..page_updates.len() {
+            v.push(PageOrdering {
+                first: *page_updates.get(j).unwrap(),
+                second: *page_updates.get(i).unwrap(),
+            });
+        }
+    }
+    v
+}
+
+fn build_all_orderings(page_updates: &[u32]) -> Vec<PageOrdering> {
+    let mut v = Vec::new();
+    for i in 0..page_updates.len() {
+        for j in i + 1..page_updates.len() {
+            v.push(PageOrdering {
+                first: *page_updates.get(i).unwrap(),
+                second: *page_updates.get(j).unwrap(),
+            });
+        }
+    }
+    v
+}
+
+// A loop in the ordering rules, e.g. [47, 53, 29] for the rules 47|53, 53|29 and 29|47. Pages
+// caught in a loop can't be put in any order that obeys all of the rules.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RuleCycle {
+    pub pages: Vec<u32>,
+}
+
+// The rules that make up the loop, e.g. "47|53, 53|29, 29|47".
+impl Display for RuleCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rules: Vec<String> = self
+            .pages
+            .iter()
+            .zip(self.pages.iter().cycle().skip(1))
+            .map(|(first, second)| format!("{first}|{second}"))
+            .collect();
+        write!(f, "{}", rules.join(", "))
+    }
+}
+
+// Put the pages of an update in an order that obeys every rule between two of them, with a
+// topological sort (Kahn's algorithm) of the rules restricted to those pages. Whenever more than
+// one page is free to go next, the one that came first in the update wins, so legal updates come
+// back unchanged.
+pub fn reorder_update(
+    update: &[u32],
+    rules: &HashSet<PageOrdering>,
+) -> Result<Vec<u32>, RuleCycle> {
+    // Work with indices into the update. `blockers[i]` counts the pages that still have to be
+    // placed before page i.
+    let mut successors = vec![Vec::new(); update.len()];
+    let mut blockers = vec![0; update.len()];
+    for (i, &first) in update.iter().enumerate() {
+        for (j, &second) in update.iter().enumerate() {
+            if i != j && rules.contains(&PageOrdering { first, second }) {
+                successors[i].push(j);
+                blockers[j] += 1;
+            }
+        }
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..update.len())
+        .filter(|&i| blockers[i] == 0)
+        .map(Reverse)
+        .collect();
+    let mut ordered = Vec::with_capacity(update.len());
+    while let Some(Reverse(i)) = ready.pop() {
+        ordered.push(update[i]);
+        for &j in successors[i].iter() {
+            blockers[j] -= 1;
+            if blockers[j] == 0 {
+                ready.push(Reverse(j));
+            }
+        }
+    }
+
+    if ordered.len() == update.len() {
+        Ok(ordered)
+    } else {
+        Err(find_cycle(update, &successors, &blockers))
+    }
+}
+
+// After the sort gets stuck, every page that's left is blocked by another page that's left. So
+// walking backwards from any of them through blocking pages has to come round in a loop.
+fn find_cycle(update: &[u32], successors: &[Vec<usize>], blockers: &[usize]) -> RuleCycle {
+    let blocker_of = |j: usize| {
+        // Unwrap: a page with blockers left has at least one unplaced page before it.
+        (0..update.len())
+            .find(|&i| blockers[i] > 0 && successors[i].contains(&j))
+            .unwrap()
+    };
+    let start = (0..update.len()).find(|&i| blockers[i] > 0).unwrap();
+    let mut walk = vec![start];
+    let mut position_in_walk = vec![None; update.len()];
+    position_in_walk[start] = Some(0);
+    loop {
+        let previous = blocker_of(*walk.last().unwrap());
+        if let Some(loop_start) = position_in_walk[previous] {
+            // The walk went backwards, so turn the loop around to follow the rules.
+            return RuleCycle {
+                pages: walk[loop_start..]
+                    .iter()
+                    .rev()
+                    .map(|&i| update[i])
+                    .collect(),
+            };
+        }
+        position_in_walk[previous] = Some(walk.len());
+        walk.push(previous);
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum UpdateLegality {
+    Legal,
+    Illegal,
+}
+
+/* Given a vec of updates, return a new vec with only updates matching the provided legality.
+* For legal updates, we return those which obey all of the ordering rules.
+* For illegal updates, we return those which do not.
+*/
+fn get_update_subset(
+    updates: &[Vec<u32>],
+    rules: &HashSet<PageOrdering>,
+    legality: UpdateLegality,
+) -> Vec<Vec<u32>> {
+    let mut filtered_updates = Vec::new();
+    match legality {
+        UpdateLegality::Legal => updates.iter().for_each(|pages: &Vec<u32>| {
+            // Algorithm: for each update, build all pairs of numbers that correspond to orderings
+            // e.g., for an update 75,47,61,53,29, generate pairs:
+            // -> (75, 47), (75, 61), (75, 53), (75, 29)
+            // -> (47, 61), (47, 53), (47, 29)
+            // -> (61, 53), (61, 29)
+            // Then determine if all of these update orderings are legal by looking up the
+            // ordering in the ruleset.
+            let page_orders = build_all_orderings(pages);
+            if page_orders
+                .iter()
+                .all(|page_order| rules.contains(page_order))
+            {
+                filtered_updates.push(pages.clone());
+            }
+        }),
+        UpdateLegality::Illegal => updates.iter().for_each(|pages: &Vec<u32>| {
+            let reversed_orders = build_reversed_orderings(pages);
+            if reversed_orders
+                .iter()
+                .any(|page_order| rules.contains(page_order))
+            {
+                filtered_updates.push(pages.clone());
+            }
+        }),
+    }
+    filtered_updates
+}
+
+// Updates always have an odd number of pages, which parse_sections checks.
+fn get_middle_page_number_sum(updates: &[Vec<u32>]) -> u32 {
+    updates
+        .iter()
+        .map(|page_updates| {
+            // The middle index is equal to len() / 2 in integer division.
+            page_updates.get(page_updates.len() / 2).unwrap()
+        })
+        .sum()
+}
+
+pub fn sum_of_legal_middle_pages(page_updates: &[Vec<u32>], rules: &HashSet<PageOrdering>) -> u32 {
+    let legal_updates = get_update_subset(page_updates, rules, UpdateLegality::Legal);
+    get_middle_page_number_sum(&legal_updates)
+}
+
+pub fn sum_of_fixed_middle_pages(
+    page_updates: &[Vec<u32>],
+    rules: &HashSet<PageOrdering>,
+) -> Result<u32, RuleCycle> {
+    let fixed_updates = get_update_subset(page_updates, rules, UpdateLegality::Illegal)
+        .iter()
+        .map(|update| reorder_update(update, rules))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(get_middle_page_number_sum(&fixed_updates))
+}
+
+// Every update has to be sortable for part two, so loops in the rules are reported up front.
+fn check_updates_can_be_ordered(
+    page_updates: &[Vec<u32>],
+    rules: &HashSet<PageOrdering>,
+) -> Result<(), AocError> {
+    for update in page_updates {
+        if let Err(cycle) = reorder_update(update, rules) {
+            let pages: Vec<String> = update.iter().map(|page| page.to_string()).collect();
+            return Err(AocError::validation(format!(
+                "the rules for update {} contain a cycle: {cycle}",
+                pages.join(",")
+            )));
+        }
+    }
+    Ok(())
+}
+
+// The puzzle input is the rules, then a blank line, then the updates. When the rules and updates
+// are passed as separate files, the runner joins them with a blank line. Unlike Day5::parse, this
+// doesn't mind rules that loop, so that the loops can be looked at.
+pub fn parse_rules_and_updates(
+    raw_input: &str,
+) -> Result<(HashSet<PageOrdering>, Vec<Vec<u32>>), AocError> {
+    let sections = parse_sections(raw_input)?;
+    match (sections.rules, sections.updates) {
+        (Some(rules), Some(updates)) => Ok((rules, updates)),
+        (None, _) => Err(AocError::validation("the input has no page ordering rules")),
+        (_, None) => Err(AocError::validation("the input has no page updates")),
+    }
+}
+
+impl Solution for Day5 {
+    type Input = (HashSet<PageOrdering>, Vec<Vec<u32>>);
+
+    fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError> {
+        let (rules, updates) = parse_rules_and_updates(raw_input)?;
+        check_updates_can_be_ordered(&updates, &rules)?;
+        Ok((rules, updates))
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Option<String> {
+        let (rules, page_updates) = input;
+        Some(sum_of_legal_middle_pages(page_updates, rules).to_string())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Option<String> {
+        let (rules, page_updates) = input;
+        let sum = sum_of_fixed_middle_pages(page_updates, rules)
+            .expect("parse checks that every update can be ordered");
+        Some(sum.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(crate) const EXAMPLE: &str = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\n\n75,47,61,53,29\n97,61,53,29,13\n75,29,13\n75,97,47,61,53\n61,13,29\n97,13,75,29,47\n";
+
+    #[test]
+    fn test_example_answers() {
+        let input = Day5.parse(EXAMPLE).unwrap();
+        assert_eq!(input.0.len(), 21);
+        assert_eq!(input.1.len(), 6);
+        assert_eq!(Day5.part_one(&input), Some("143".to_string()));
+        assert_eq!(Day5.part_two(&input), Some("123".to_string()));
+    }
+
+    #[test]
+    fn test_reorder_update() {
+        let (rules, _) = Day5.parse(EXAMPLE).unwrap();
+        assert_eq!(
+            reorder_update(&[75, 97, 47, 61, 53], &rules),
+            Ok(vec![97, 75, 47, 61, 53])
+        );
+        assert_eq!(reorder_update(&[61, 13, 29], &rules), Ok(vec![61, 29, 13]));
+        assert_eq!(
+            reorder_update(&[97, 13, 75, 29, 47], &rules),
+            Ok(vec![97, 75, 47, 29, 13])
+        );
+        // Already legal, so nothing moves.
+        assert_eq!(
+            reorder_update(&[75, 47, 61, 53, 29], &rules),
+            Ok(vec![75, 47, 61, 53, 29])
+        );
+        assert_eq!(reorder_update(&[], &rules), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_rule_cycles_are_reported() {
+        let rules: HashSet<PageOrdering> = [(1, 2), (2, 3), (3, 1), (4, 1)]
+            .into_iter()
+            .map(|(first, second)| PageOrdering { first, second })
+            .collect();
+        let cycle = reorder_update(&[4, 3, 2, 1], &rules).unwrap_err();
+        let mut pages = cycle.pages.clone();
+        pages.sort();
+        assert_eq!(pages, vec![1, 2, 3]);
+        // Each page has a rule to the next one, and the last wraps round to the first.
+        for (idx, &first) in cycle.pages.iter().enumerate() {
+            let second = cycle.pages[(idx + 1) % cycle.pages.len()];
+            assert!(rules.contains(&PageOrdering { first, second }));
+        }
+        assert_eq!(
+            RuleCycle {
+                pages: vec![47, 53, 29]
+            }
+            .to_string(),
+            "47|53, 53|29, 29|47"
+        );
+        // A loop that only involves pages outside an update doesn't matter to it.
+        assert_eq!(reorder_update(&[4, 1], &rules), Ok(vec![4, 1]));
+
+        match Day5.parse("1|2\n2|3\n3|1\n\n1,2,5\n4,3,2,1,5\n") {
+            Err(AocError::Validation { message }) => {
+                assert!(message.contains("update 4,3,2,1,5"), "{message}")
+            }
+            other => panic!("Expected a validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_separate_rule_and_update_files() {
+        let (rules, updates) = EXAMPLE.split_once("\n\n").unwrap();
+        let dir = std::env::temp_dir().join(format!("day5-layouts-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, text: &str| {
+            let path = dir.join(name);
+            std::fs::write(&path, text).unwrap();
+            path.to_string_lossy().to_string()
+        };
+        let rule_file = write("rules.txt", rules);
+        let update_file = write("updates.txt", updates);
+        let combined_file = write("combined.txt", EXAMPLE);
+
+        assert_eq!(build_ruleset(&rule_file).unwrap().len(), 21);
+        assert_eq!(build_page_updates(&update_file).unwrap().len(), 6);
+        assert_eq!(
+            build_ruleset(&combined_file).unwrap(),
+            build_ruleset(&rule_file).unwrap()
+        );
+        assert_eq!(
+            build_page_updates(&combined_file).unwrap(),
+            build_page_updates(&update_file).unwrap()
+        );
+        assert!(matches!(
+            build_page_updates(&rule_file),
+            Err(AocError::Validation { .. })
+        ));
+        // The updates can come first when they're joined from separate files.
+        assert!(Day5.parse(&format!("{updates}\n{rules}")).is_ok());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_malformed_lines_are_reported() {
+        let error_at = |text: &str| match Day5.parse(text) {
+            Err(AocError::Parse { line, column, .. }) => (line, column),
+            other => panic!("Expected a parse error, got {other:?}"),
+        };
+        assert_eq!(error_at("47|53\n97|x\n\n75,47,61\n"), (2, Some(4)));
+        assert_eq!(error_at("47|53\n97\n\n75,47,61\n"), (2, Some(1)));
+        assert_eq!(error_at("47|53\n\n75,47,61\n75;47\n"), (4, Some(1)));
+        assert_eq!(error_at("47|53\n\n75,47,61\n\n1|2\n"), (5, Some(1)));
+        // No middle page.
+        assert_eq!(error_at("47|53\n\n75,47,61\n75,47\n"), (4, Some(1)));
+        assert!(matches!(
+            Day5.parse("75,47,61\n"),
+            Err(AocError::Validation { .. })
+        ));
+    }
+}
@@ -0,0 +1,362 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::PageOrdering;
+
+// The ordering rules as a directed graph, with an edge from each page to every page that has to
+// come after it. Pages that only show up in updates are nodes without any edges.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RuleGraph {
+    successors: BTreeMap<u32, BTreeSet<u32>>,
+}
+
+impl RuleGraph {
+    pub fn new(rules: &HashSet<PageOrdering>, updates: &[Vec<u32>]) -> Self {
+        let mut graph = RuleGraph::default();
+        for page in updates.iter().flatten() {
+            graph.successors.entry(*page).or_default();
+        }
+        for rule in rules {
+            graph.successors.entry(rule.second).or_default();
+            graph
+                .successors
+                .entry(rule.first)
+                .or_default()
+                .insert(rule.second);
+        }
+        graph
+    }
+
+    // Only the given pages, and the rules between two of them.
+    pub fn restricted_to(&self, pages: &[u32]) -> Self {
+        let keep: BTreeSet<u32> = pages.iter().copied().collect();
+        RuleGraph {
+            successors: keep
+                .iter()
+                .map(|page| {
+                    let successors = self
+                        .successors
+                        .get(page)
+                        .map(|next| next.intersection(&keep).copied().collect())
+                        .unwrap_or_default();
+                    (*page, successors)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn pages(&self) -> impl Iterator<Item = u32> + '_ {
+        self.successors.keys().copied()
+    }
+
+    pub fn rule_count(&self) -> usize {
+        self.successors.values().map(|next| next.len()).sum()
+    }
+
+    // Pages that no rule mentions, in either direction.
+    pub fn unconstrained_pages(&self) -> Vec<u32> {
+        let mentioned: BTreeSet<u32> = self
+            .successors
+            .iter()
+            .filter(|(_, next)| !next.is_empty())
+            .flat_map(|(page, next)| std::iter::once(*page).chain(next.iter().copied()))
+            .collect();
+        self.pages()
+            .filter(|page| !mentioned.contains(page))
+            .collect()
+    }
+
+    // Tarjan's algorithm. Every page ends up in exactly one component, and a component with more
+    // than one page (or a page with a rule putting it before itself) is a loop. Pages within a
+    // component are sorted, and components come out with the later pages first.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<u32>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            next_index: 0,
+            index: BTreeMap::new(),
+            low_link: BTreeMap::new(),
+            stack: Vec::new(),
+            on_stack: BTreeSet::new(),
+            components: Vec::new(),
+        };
+        for page in self.pages() {
+            if !tarjan.index.contains_key(&page) {
+                tarjan.visit(page);
+            }
+        }
+        tarjan.components
+    }
+
+    // The components that are actually loops. A rule like 5|5 makes a loop of one page.
+    pub fn cycles(&self) -> Vec<Vec<u32>> {
+        self.strongly_connected_components()
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || self.successors[&component[0]].contains(&component[0])
+            })
+            .collect()
+    }
+
+    // The longest run of pages where a rule puts each one before the next, or None if the rules
+    // have a loop (and so chains of any length).
+    pub fn longest_chain(&self) -> Option<Vec<u32>> {
+        if !self.cycles().is_empty() {
+            return None;
+        }
+        // Components come out of Tarjan's algorithm in reverse topological order, so every page's
+        // successors have been measured by the time we get to it.
+        let mut best_next: BTreeMap<u32, (usize, Option<u32>)> = BTreeMap::new();
+        for component in self.strongly_connected_components() {
+            let page = component[0];
+            let longest = self.successors[&page]
+                .iter()
+                .map(|next| (best_next[next].0 + 1, Some(*next)))
+                .max()
+                .unwrap_or((1, None));
+            best_next.insert(page, longest);
+        }
+        let (_, start) = best_next
+            .iter()
+            .map(|(page, (length, _))| (*length, *page))
+            .max()?;
+        Some(std::iter::successors(Some(start), |page| best_next[page].1).collect())
+    }
+
+    // The graph in Graphviz DOT format. Pages are listed in `order` when one is given (e.g. an
+    // update), and rules that the order breaks are drawn in red.
+    pub fn to_dot(&self, name: &str, order: Option<&[u32]>) -> String {
+        let position: BTreeMap<u32, usize> = order
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+            .map(|(idx, page)| (*page, idx))
+            .collect();
+        let mut dot = format!("digraph {name} {{\n");
+        let pages: Vec<u32> = match order {
+            Some(order) => order.to_vec(),
+            None => self.pages().collect(),
+        };
+        for page in pages.iter() {
+            dot.push_str(&format!("    {page};\n"));
+        }
+        for (first, next) in self.successors.iter() {
+            for second in next {
+                let broken = matches!(
+                    (position.get(first), position.get(second)),
+                    (Some(a), Some(b)) if a > b
+                );
+                let style = if broken { " [color=red]" } else { "" };
+                dot.push_str(&format!("    {first} -> {second}{style};\n"));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+struct Tarjan<'a> {
+    graph: &'a RuleGraph,
+    next_index: usize,
+    index: BTreeMap<u32, usize>,
+    low_link: BTreeMap<u32, usize>,
+    stack: Vec<u32>,
+    on_stack: BTreeSet<u32>,
+    components: Vec<Vec<u32>>,
+}
+
+impl Tarjan<'_> {
+    // Recursive, but puzzle inputs only have around a hundred pages.
+    fn visit(&mut self, page: u32) {
+        self.index.insert(page, self.next_index);
+        self.low_link.insert(page, self.next_index);
+        self.next_index += 1;
+        self.stack.push(page);
+        self.on_stack.insert(page);
+
+        for &next in self.graph.successors[&page].iter() {
+            if !self.index.contains_key(&next) {
+                self.visit(next);
+                let low = self.low_link[&page].min(self.low_link[&next]);
+                self.low_link.insert(page, low);
+            } else if self.on_stack.contains(&next) {
+                let low = self.low_link[&page].min(self.index[&next]);
+                self.low_link.insert(page, low);
+            }
+        }
+
+        if self.low_link[&page] == self.index[&page] {
+            let mut component = Vec::new();
+            loop {
+                // Unwrap: `page` itself is still on the stack.
+                let member = self.stack.pop().unwrap();
+                self.on_stack.remove(&member);
+                component.push(member);
+                if member == page {
+                    break;
+                }
+            }
+            component.sort();
+            self.components.push(component);
+        }
+    }
+}
+
+fn page_count(count: usize) -> String {
+    match count {
+        1 => "1 page".to_string(),
+        _ => format!("{count} pages"),
+    }
+}
+
+fn page_list(pages: &[u32], separator: &str) -> String {
+    let pages: Vec<String> = pages.iter().map(|page| page.to_string()).collect();
+    pages.join(separator)
+}
+
+// A summary of the rules: loops, pages that aren't constrained at all, and the longest chains.
+// The real input's rules loop as a whole, so the longest chain is also given per update.
+pub fn analysis_report(rules: &HashSet<PageOrdering>, updates: &[Vec<u32>]) -> String {
+    let graph = RuleGraph::new(rules, updates);
+    let mut report = format!(
+        "{} rules between {} pages, {} updates\n",
+        graph.rule_count(),
+        graph.pages().count(),
+        updates.len()
+    );
+
+    let cycles = graph.cycles();
+    if cycles.is_empty() {
+        report.push_str("Loops: none\n");
+    } else {
+        report.push_str(&format!("Loops: {}\n", cycles.len()));
+        for cycle in cycles.iter() {
+            report.push_str(&format!(
+                "    {}: {}\n",
+                page_count(cycle.len()),
+                page_list(cycle, ", ")
+            ));
+        }
+    }
+
+    let unconstrained = graph.unconstrained_pages();
+    report.push_str(&format!(
+        "Pages with no rules: {}\n",
+        if unconstrained.is_empty() {
+            "none".to_string()
+        } else {
+            page_list(&unconstrained, ", ")
+        }
+    ));
+
+    report.push_str(&format!(
+        "Longest chain: {}\n",
+        match graph.longest_chain() {
+            Some(chain) => format!("{}: {}", page_count(chain.len()), page_list(&chain, " -> ")),
+            None => "none, the rules loop".to_string(),
+        }
+    ));
+
+    // The first of the longest, if there are several.
+    let longest_in_update = updates
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, update)| {
+            let chain = graph.restricted_to(update).longest_chain()?;
+            Some((chain.len(), std::cmp::Reverse(idx), chain))
+        })
+        .max();
+    if let Some((length, std::cmp::Reverse(idx), chain)) = longest_in_update {
+        report.push_str(&format!(
+            "Longest chain within an update: update {}, {}: {}\n",
+            idx + 1,
+            page_count(length),
+            page_list(&chain, " -> ")
+        ));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(pairs: &[(u32, u32)]) -> HashSet<PageOrdering> {
+        pairs
+            .iter()
+            .map(|&(first, second)| PageOrdering { first, second })
+            .collect()
+    }
+
+    #[test]
+    fn test_components_and_cycles() {
+        let graph = RuleGraph::new(
+            &rules(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 4)]),
+            &[vec![1, 6]],
+        );
+        let mut components = graph.strongly_connected_components();
+        components.sort();
+        assert_eq!(components, vec![vec![1, 2, 3], vec![4, 5], vec![6]]);
+        assert_eq!(graph.cycles().len(), 2);
+        assert_eq!(graph.unconstrained_pages(), vec![6]);
+        assert_eq!(graph.longest_chain(), None);
+        let report = analysis_report(
+            &rules(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 4)]),
+            &[vec![1, 6], vec![2, 3]],
+        );
+        assert!(report.contains("Loops: 2\n    2 pages: 4, 5\n"));
+        assert!(report.contains("Pages with no rules: 6\n"));
+        assert!(report.contains("Longest chain: none, the rules loop\n"));
+        assert!(report.contains("within an update: update 2, 2 pages: 2 -> 3\n"));
+        // Dropping page 3 breaks the first loop.
+        let graph = graph.restricted_to(&[1, 2, 4]);
+        assert!(graph.cycles().is_empty());
+        assert_eq!(graph.rule_count(), 1);
+        assert_eq!(graph.longest_chain(), Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_self_loop() {
+        let rules = rules(&[(5, 5), (1, 2)]);
+        let updates = [vec![1, 2, 5]];
+        let graph = RuleGraph::new(&rules, &updates);
+        assert_eq!(graph.cycles(), vec![vec![5]]);
+        assert_eq!(graph.longest_chain(), None);
+        assert_eq!(
+            graph.restricted_to(&[1, 2]).longest_chain(),
+            Some(vec![1, 2])
+        );
+        let report = analysis_report(&rules, &updates);
+        assert!(report.contains("Loops: 1\n    1 page: 5\n"));
+        assert!(report.contains("Longest chain: none, the rules loop\n"));
+    }
+
+    #[test]
+    fn test_example_analysis() {
+        let (rules, updates) = crate::parse_rules_and_updates(crate::tests::EXAMPLE).unwrap();
+        let graph = RuleGraph::new(&rules, &updates);
+        assert_eq!(
+            graph.longest_chain(),
+            Some(vec![97, 75, 47, 61, 53, 29, 13])
+        );
+        let report = analysis_report(&rules, &updates);
+        assert!(report.starts_with("21 rules between 7 pages, 6 updates\n"));
+        assert!(report.contains("Pages with no rules: none\n"));
+        assert!(report.contains(
+            "Longest chain within an update: update 1, 5 pages: 75 -> 47 -> 61 -> 53 -> 29\n"
+        ));
+    }
+
+    #[test]
+    fn test_update_dot_marks_broken_rules() {
+        let graph = RuleGraph::new(&rules(&[(1, 2), (2, 3), (1, 3), (4, 1)]), &[]);
+        let dot = graph
+            .restricted_to(&[3, 1, 2])
+            .to_dot("update_1", Some(&[3, 1, 2]));
+        assert_eq!(
+            dot,
+            "digraph update_1 {\n    3;\n    1;\n    2;\n    1 -> 2;\n    1 -> 3 [color=red];\n    2 -> 3 [color=red];\n}\n"
+        );
+        let dot = graph.to_dot("rules", None);
+        assert!(dot.contains("    4 -> 1;\n"));
+        assert!(!dot.contains("red"));
+    }
+}
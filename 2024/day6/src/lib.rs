@@ -0,0 +1,379 @@
+use std::collections::HashSet;
+use std::fmt::Debug;
+
+use commons::{
+    geometry::{Direction, Point},
+    grid::Grid,
+    io_utilities::read_file_to_string,
+    solution::Solution,
+    AocError,
+};
+
+pub mod loop_finder;
+pub mod loop_report;
+pub mod replay;
+pub mod simulator;
+
+pub struct Day6;
+
+// For the modes with a --format flag, which take table or json.
+pub fn invalid_format(format: Option<&str>) -> AocError {
+    AocError::argument(format!(
+        "Invalid format '{}', expected table or json",
+        format.unwrap_or_default()
+    ))
+}
+
+const VISITED_POS: char = 'X';
+
+#[derive(Debug)]
+enum NextGuardAction {
+    Advance,
+    Turn,
+    ExitLab,
+}
+
+// The map only holds the floor ('.'), obstacles ('#') and visited positions ('X'). The guard's
+// arrow is taken off the map when it's parsed, and its position and direction are tracked here.
+#[derive(Clone)]
+pub struct Lab {
+    map: Grid<char>,
+    guard_position: Point,
+    guard_direction: Direction,
+    starting_position: Point,
+    starting_direction: Direction,
+    guard_path: HashSet<(Point, Direction)>,
+}
+
+impl Debug for Lab {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Draw the guard back onto a copy of the map.
+        let mut map = self.map.clone();
+        map[self.guard_position] = self.guard_direction.arrow().unwrap();
+        let _ = f.debug_struct("Lab:\n").finish();
+        write!(f, "{}\nGuard Path Map: {:?}", map, self.guard_path)
+    }
+}
+
+impl Lab {
+    // Where the guard would be after one more step, or None if that step leaves the lab.
+    fn next_position(&self) -> Option<Point> {
+        self.map.step(self.guard_position, self.guard_direction)
+    }
+
+    fn reset_to_starting_state(&mut self) {
+        self.guard_position = self.starting_position;
+        self.guard_direction = self.starting_direction;
+        self.guard_path.clear();
+        self.guard_path
+            .insert((self.starting_position, self.starting_direction));
+
+        // Reset all visited positions to their original state.
+        self.map.iter_mut().for_each(|(_, pos)| {
+            if *pos == VISITED_POS {
+                *pos = '.';
+            }
+        });
+    }
+}
+
+pub fn build_lab_from_file(fname: &str) -> Result<Lab, AocError> {
+    build_lab_from_string(&read_file_to_string(fname)?)
+}
+
+fn find_starting_guard_pos(map: &Grid<char>) -> Result<(Point, Direction), AocError> {
+    // Assumes only 1 guard char in the input, which is fine.
+    let position = map
+        .find(|c| Direction::from_arrow(*c).is_some())
+        .ok_or_else(|| AocError::validation("the lab map has no guard in it"))?;
+    Ok((
+        position.into(),
+        Direction::from_arrow(map[position]).unwrap(),
+    ))
+}
+
+fn mark_position_visited(lab: &mut Lab, position: Point) {
+    lab.map[position] = VISITED_POS;
+}
+
+fn next_step_is_blocked(lab: &Lab) -> bool {
+    lab.next_position()
+        .is_some_and(|position| lab.map[position] == '#')
+}
+
+fn next_step_exits_lab(lab: &Lab) -> bool {
+    lab.next_position().is_none()
+}
+
+fn next_guard_action(lab: &Lab) -> NextGuardAction {
+    if next_step_exits_lab(lab) {
+        return NextGuardAction::ExitLab;
+    } else if next_step_is_blocked(lab) {
+        return NextGuardAction::Turn;
+    }
+    NextGuardAction::Advance
+}
+
+// Move the guard one step in the direction it's facing, and return the old guard position so
+// that it can then be marked as visited by the caller.
+fn advance_guard(lab: &mut Lab) -> Point {
+    let old_position = lab.guard_position;
+    lab.guard_position = lab
+        .next_position()
+        .expect("the guard only advances when the next step is inside the lab");
+    old_position
+}
+
+// The guard always turns right, by 90 degrees.
+fn rotate_guard(lab: &mut Lab) {
+    lab.guard_direction = lab.guard_direction.rotate_cw();
+}
+
+// What happened on one move of the guard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuardStep {
+    Walking,
+    Exited,
+    Looped,
+}
+
+// Move the guard once: a step forward, a turn, or out of the lab. The old position is marked as
+// visited, and the new position and direction are added to the path to spot loops.
+pub fn step_guard(lab: &mut Lab) -> GuardStep {
+    match next_guard_action(lab) {
+        NextGuardAction::Advance => {
+            let old_position = advance_guard(lab);
+            mark_position_visited(lab, old_position);
+        }
+        NextGuardAction::Turn => {
+            rotate_guard(lab);
+        }
+        NextGuardAction::ExitLab => {
+            // mark the guard's current position as visited (because they're on the edge, they
+            // will go out of the lab)
+            mark_position_visited(lab, lab.guard_position);
+            return GuardStep::Exited;
+        }
+    }
+    // If the guard has been here before, facing the same way, it's going round in a loop.
+    let state = (lab.guard_position, lab.guard_direction);
+    if lab.guard_path.contains(&state) {
+        return GuardStep::Looped;
+    }
+    lab.guard_path.insert(state);
+    GuardStep::Walking
+}
+
+// Walk the guard until it leaves the lab or loops. Returns true if it loops.
+pub fn calculate_guard_path(lab: &mut Lab) -> bool {
+    loop {
+        match step_guard(lab) {
+            GuardStep::Walking => {}
+            GuardStep::Exited => break false,
+            GuardStep::Looped => break true,
+        }
+    }
+}
+
+// DOES THIS CODE DO ANYTHING USEFUL?? NO IDEA.
+// fn calculate_guard_loops_copilot_auto(lab: &Lab) {
+//     // Calculate the number of loops the guard makes by finding the first node that is repeated in
+//     // the guard's path list.
+//     let mut guard_list_iter = lab.guard_list.iter();
+//     let mut guard_list_iter_peek = guard_list_iter.clone();
+//     let mut guard_list_iter_peek_peek = guard_list_iter_peek.clone();
+//     loop {
+//         let current_node = guard_list_iter.next().unwrap();
+//         let peeked_node = guard_list_iter_peek.next().unwrap();
+//         let peeked_peeked_node = guard_list_iter_peek_peek.next().unwrap();
+//         if peeked_node == peeked_peeked_node {
+//             // We've found a loop, so we can now calculate the length of the loop by counting the
+//             // number of nodes between the current node and the peeked node.
+//             let mut loop_length = 1;
+//             loop {
+//                 let next_node = guard_list_iter.next().unwrap();
+//                 loop_length += 1;
+//                 if next_node == peeked_node {
+//                     break;
+//                 }
+//             }
+//             println!("Found a loop of length {}", loop_length);
+//             break;
+//         }
+//     }
+// }
+
+pub fn build_lab_from_string(raw_string: &str) -> Result<Lab, AocError> {
+    let mut map = Grid::parse(raw_string)?;
+    let (current_pos, starting_direction) = find_starting_guard_pos(&map)?;
+    map[current_pos] = '.';
+    Ok(Lab {
+        map,
+        guard_position: current_pos,
+        guard_direction: starting_direction,
+        starting_position: current_pos,
+        starting_direction,
+        guard_path: HashSet::new(),
+    })
+}
+
+pub fn count_visited_positions(lab: &Lab) -> usize {
+    lab.map
+        .cells()
+        .iter()
+        .filter(|&pos| *pos == VISITED_POS)
+        .count()
+}
+
+impl Solution for Day6 {
+    type Input = Lab;
+
+    // Parsing also walks the guard's original path, since both parts start from it.
+    fn parse(&self, raw_input: &str) -> Result<Self::Input, AocError> {
+        let mut lab = build_lab_from_string(raw_input)?;
+        calculate_guard_path(&mut lab);
+        Ok(lab)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> Option<String> {
+        Some(count_visited_positions(input).to_string())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> Option<String> {
+        Some(loop_finder::loop_obstacles(input).len().to_string())
+    }
+}
+
+#[cfg(test)]
+fn build_test_lab() -> Lab {
+    build_lab_from_string(&read_example_input()).unwrap()
+}
+
+#[cfg(test)]
+fn read_example_input() -> String {
+    use commons::input_store::{InputKind, InputStore};
+    InputStore::from_env()
+        .and_then(|store| store.read(2024, 6, InputKind::Example(1)))
+        .unwrap()
+}
+
+#[cfg(test)]
+fn build_test_lab_from_string(test_string: &str) -> Lab {
+    build_lab_from_string(test_string).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*; // bring in all the functionality in the word_search module
+
+    #[test]
+    fn test_next_step_is_blocked() {
+        // Test a basic case for being blocked when going left, right, up, and down.
+        let lab = build_test_lab_from_string("...\n.>#\n...\n");
+        assert!(next_step_is_blocked(&lab));
+        let lab = build_test_lab_from_string("...\n.>.\n...\n");
+        assert!(!next_step_is_blocked(&lab));
+        let lab = build_test_lab_from_string("...\n.v.\n.#.\n");
+        assert!(next_step_is_blocked(&lab));
+        let lab = build_test_lab_from_string("...\n.v.\n...\n");
+        assert!(!next_step_is_blocked(&lab));
+        let lab = build_test_lab_from_string(".#.\n.^.\n...\n");
+        assert!(next_step_is_blocked(&lab));
+        let lab = build_test_lab_from_string("...\n.^.\n...\n");
+        assert!(!next_step_is_blocked(&lab));
+        let lab = build_test_lab_from_string("#<.\n...\n...\n");
+        assert!(next_step_is_blocked(&lab));
+        let lab = build_test_lab_from_string(".<.\n...\n...\n");
+        assert!(!next_step_is_blocked(&lab));
+    }
+
+    #[test]
+    fn test_next_step_exits_lab() {
+        let lab = build_test_lab_from_string(".^.\n.##\n...\n");
+        assert!(next_step_exits_lab(&lab));
+        let lab = build_test_lab_from_string("...\n.^#\n...\n");
+        assert!(!next_step_exits_lab(&lab));
+        let lab = build_test_lab_from_string("..>\n.##\n...\n");
+        assert!(next_step_exits_lab(&lab));
+        let lab = build_test_lab_from_string("...\n.##\n..>\n");
+        assert!(next_step_exits_lab(&lab));
+        let lab = build_test_lab_from_string("...\n.##\n.>.\n");
+        assert!(!next_step_exits_lab(&lab));
+        let lab = build_test_lab_from_string("<..\n.##\n...\n");
+        assert!(next_step_exits_lab(&lab));
+        let lab = build_test_lab_from_string(".<.\n.##\n...\n");
+        assert!(!next_step_exits_lab(&lab));
+        let lab = build_test_lab_from_string("...\n.v#\n...\n");
+        assert!(!next_step_exits_lab(&lab));
+        let lab = build_test_lab_from_string("...\n..#\n.v.\n");
+        assert!(next_step_exits_lab(&lab));
+    }
+
+    #[test]
+    fn test_rotate_guard() {
+        let mut lab = build_test_lab();
+        // Loop over all guard directions and ensure that rotating always gives the correct
+        // next direction.
+        for (direction, expected) in [
+            (Direction::Right, Direction::Down),
+            (Direction::Left, Direction::Up),
+            (Direction::Up, Direction::Right),
+            (Direction::Down, Direction::Left),
+        ] {
+            lab.guard_direction = direction;
+            rotate_guard(&mut lab);
+            assert_eq!(lab.guard_direction, expected);
+        }
+    }
+
+    #[test]
+    fn test_advance_guard() {
+        // Loop over all guard directions and ensure that regardless of the initial guard
+        // direction, it advances by one step and the old position isn't marked as "X" yet.
+        for direction in Direction::CARDINAL {
+            let mut lab = build_test_lab();
+            lab.guard_direction = direction;
+            let expected_new_pos = lab.guard_position.step(direction).unwrap();
+            let old_pos = advance_guard(&mut lab);
+            assert_eq!(lab.guard_position, expected_new_pos);
+            assert_eq!(lab.guard_direction, direction);
+            assert_ne!(lab.map[old_pos], VISITED_POS);
+        }
+    }
+
+    #[test]
+    fn test_guard_is_taken_off_the_map() {
+        let lab = build_test_lab_from_string("...\n.<.\n...\n");
+        assert_eq!(lab.guard_position, Point::new(1, 1));
+        assert_eq!(lab.guard_direction, Direction::Left);
+        assert_eq!(lab.map[lab.guard_position], '.');
+        assert!(format!("{lab:?}").contains(".<."));
+    }
+
+    #[test]
+    fn test_example_answers() {
+        let lab = Day6.parse(&read_example_input()).unwrap();
+        assert_eq!(Day6.part_one(&lab), Some("41".to_string()));
+        assert_eq!(Day6.part_two(&lab), Some("6".to_string()));
+    }
+
+    #[test]
+    fn test_build_lab_errors() {
+        assert!(matches!(
+            build_lab_from_string("...\n...\n"),
+            Err(AocError::Validation { .. })
+        ));
+        assert!(matches!(
+            build_lab_from_string("...\n.^\n...\n"),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+        assert!(build_lab_from_string("").is_err());
+    }
+
+    #[test]
+    fn test_mark_position_visited() {
+        let mut lab = build_test_lab();
+        mark_position_visited(&mut lab, Point::new(0, 0));
+        assert_eq!(lab.map[(0, 0)], 'X');
+    }
+}
@@ -0,0 +1,310 @@
+use commons::geometry::{Direction, Point};
+use commons::grid::Grid;
+
+use crate::{Lab, VISITED_POS};
+
+// Part two, done quickly. Instead of walking the guard one cell at a time, a jump table says where
+// the guard stops (just in front of the next obstacle) from every cell in every direction, so a
+// walk is one lookup per turn. The extra obstacle isn't in the table: each jump just checks
+// whether it's in the way. Candidates are split across threads, and they share the table.
+
+const EXIT: u32 = u32::MAX;
+
+fn direction_index(direction: Direction) -> usize {
+    match direction {
+        Direction::Up => 0,
+        Direction::Right => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+        _ => unreachable!("the guard only walks in the four cardinal directions"),
+    }
+}
+
+pub struct JumpTable {
+    width: usize,
+    height: usize,
+    // Indexed by cell, then by direction_index. The cell where the guard stops, or EXIT if it
+    // walks off the map.
+    stops: Vec<[u32; 4]>,
+}
+
+impl JumpTable {
+    pub fn new(map: &Grid<char>) -> Self {
+        let (width, height) = (map.width(), map.height());
+        let mut stops = vec![[EXIT; 4]; map.len()];
+        // Each direction is filled in starting from the edge the guard would walk off, so the
+        // cell one step ahead is always done before the cell behind it.
+        for direction in Direction::CARDINAL {
+            let d = direction_index(direction);
+            let mut positions: Vec<(usize, usize)> = map.positions().collect();
+            if matches!(direction, Direction::Down | Direction::Right) {
+                positions.reverse();
+            }
+            for position in positions {
+                let index = map.index_of(position);
+                stops[index][d] = match map.step(position.into(), direction) {
+                    None => EXIT,
+                    Some(ahead) if map[ahead] == '#' => index as u32,
+                    Some(ahead) => stops[map.index_of(ahead.into())][d],
+                };
+            }
+        }
+        JumpTable {
+            width,
+            height,
+            stops,
+        }
+    }
+
+    fn point(&self, index: u32) -> Point {
+        let index = index as usize;
+        Point::new(index / self.width, index % self.width)
+    }
+
+    fn index(&self, point: Point) -> u32 {
+        (point.row * self.width + point.col) as u32
+    }
+
+    // Where the guard stops walking from `from` in `direction`, with an extra obstacle at
+    // `obstacle`. None if it walks off the map.
+    fn stop_with_obstacle(
+        &self,
+        from: Point,
+        direction: Direction,
+        obstacle: Point,
+    ) -> Option<Point> {
+        let stop = self.stops[self.index(from) as usize][direction_index(direction)];
+        let (row_delta, col_delta) = direction.offset();
+        // How many steps ahead the obstacle is, if it's on the guard's line at all.
+        let steps_to_obstacle = if col_delta == 0 && obstacle.col == from.col {
+            (obstacle.row as isize - from.row as isize) * row_delta
+        } else if row_delta == 0 && obstacle.row == from.row {
+            (obstacle.col as isize - from.col as isize) * col_delta
+        } else {
+            0
+        };
+        if steps_to_obstacle > 0 {
+            let steps_to_stop = if stop == EXIT {
+                // Walking off the map counts as stopping one step past the edge.
+                let edge_distance = match direction {
+                    Direction::Up => from.row,
+                    Direction::Down => self.height - 1 - from.row,
+                    Direction::Left => from.col,
+                    _ => self.width - 1 - from.col,
+                };
+                edge_distance as isize + 1
+            } else {
+                let stop = self.point(stop);
+                from.row.abs_diff(stop.row) as isize + from.col.abs_diff(stop.col) as isize
+            };
+            if steps_to_obstacle <= steps_to_stop {
+                return from.step_by(direction, steps_to_obstacle as usize - 1);
+            }
+        }
+        (stop != EXIT).then(|| self.point(stop))
+    }
+
+    // Whether the guard walks in a loop once `obstacle` is added. `seen` is scratch space with a
+    // slot per cell and direction; slots equal to `stamp` are states seen on this walk, which saves
+    // clearing it between candidates.
+    fn loops_with_obstacle(
+        &self,
+        start: Point,
+        direction: Direction,
+        obstacle: Point,
+        seen: &mut [u32],
+        stamp: u32,
+    ) -> bool {
+        let (mut position, mut direction) = (start, direction);
+        loop {
+            let Some(stop) = self.stop_with_obstacle(position, direction, obstacle) else {
+                return false;
+            };
+            // The guard turns where it stops, so the states at stops are the only ones that need
+            // remembering: if one comes round again, so will everything after it.
+            let slot = self.index(stop) as usize * 4 + direction_index(direction);
+            if seen[slot] == stamp {
+                return true;
+            }
+            seen[slot] = stamp;
+            position = stop;
+            direction = direction.rotate_cw();
+        }
+    }
+}
+
+// Every position where one extra obstacle traps the guard in a loop, in row-major order. Only the
+// positions on the guard's original path (apart from its start) can change anything, so those are
+// the candidates. `lab` has to have had its path walked already.
+pub fn loop_obstacles(lab: &Lab) -> Vec<Point> {
+    let table = JumpTable::new(&lab.map);
+    let candidates: Vec<Point> = lab
+        .map
+        .iter()
+        .filter(|(position, cell)| {
+            **cell == VISITED_POS && Point::from(*position) != lab.starting_position
+        })
+        .map(|(position, _)| position.into())
+        .collect();
+
+    split_across_threads(&candidates, |chunk| {
+        let mut seen = vec![0; lab.map.len() * 4];
+        chunk
+            .iter()
+            .enumerate()
+            .filter(|(idx, obstacle)| {
+                table.loops_with_obstacle(
+                    lab.starting_position,
+                    lab.starting_direction,
+                    **obstacle,
+                    &mut seen,
+                    *idx as u32 + 1,
+                )
+            })
+            .map(|(_, obstacle)| *obstacle)
+            .collect()
+    })
+}
+
+// Split `items` into one chunk per core, run `work` on each chunk on its own thread, and put the
+// results back together in order.
+pub(crate) fn split_across_threads<T, R, F>(items: &[T], work: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&[T]) -> Vec<R> + Sync,
+{
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = items.len().div_ceil(threads).max(1);
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let work = &work;
+                scope.spawn(move || work(chunk))
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("a worker thread panicked"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    use crate::{build_lab_from_string, calculate_guard_path};
+    use commons::test_utils::XorShift;
+
+    // The straightforward way to do part two, which re-walks a copy of the whole lab for every
+    // candidate. Far too slow for the real input, but handy to check loop_obstacles against.
+    fn count_all_possible_guard_loops(lab: &Lab) -> usize {
+        // For each position the guard takes, place an obstacle in front of it and then calculate
+        // if the guard will loop forever without exiting the board. If it does, then we've found
+        // a loop.
+        let mut loops = 0;
+        let mut unique_starts = HashSet::new();
+        for (pos, _) in lab.guard_path.iter() {
+            if *pos != lab.starting_position && !unique_starts.contains(pos) {
+                unique_starts.insert(pos);
+                let mut lab_copy = lab.clone();
+                lab_copy.reset_to_starting_state();
+                lab_copy.map[*pos] = '#';
+
+                // Calculate path with the existing function.
+                if calculate_guard_path(&mut lab_copy) {
+                    loops += 1;
+                }
+            }
+        }
+        loops
+    }
+
+    // A random map, about one cell in ten an obstruction, with the guard somewhere on it.
+    fn random_map(seed: u64, size: usize) -> String {
+        let mut rng = XorShift::new(seed);
+        let mut rows: Vec<Vec<char>> = (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| if rng.below(10) == 0 { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let (row, col) = (
+            rng.below(size as u64) as usize,
+            rng.below(size as u64) as usize,
+        );
+        rows[row][col] = *rng.choose(&['^', '>', 'v', '<']);
+        rows.iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect()
+    }
+
+    #[test]
+    fn test_jump_table() {
+        let lab = build_lab_from_string("..#..\n.....\n#.^..\n.....\n...#.\n").unwrap();
+        let table = JumpTable::new(&lab.map);
+        let stop = |point: Point, direction| {
+            let index = table.stops[table.index(point) as usize][direction_index(direction)];
+            (index != EXIT).then(|| table.point(index))
+        };
+        assert_eq!(
+            stop(Point::new(2, 2), Direction::Up),
+            Some(Point::new(1, 2))
+        );
+        assert_eq!(
+            stop(Point::new(2, 2), Direction::Left),
+            Some(Point::new(2, 1))
+        );
+        assert_eq!(stop(Point::new(2, 2), Direction::Right), None);
+        assert_eq!(
+            stop(Point::new(0, 3), Direction::Down),
+            Some(Point::new(3, 3))
+        );
+        // Right in front of an obstacle, the guard doesn't move.
+        assert_eq!(
+            stop(Point::new(1, 2), Direction::Up),
+            Some(Point::new(1, 2))
+        );
+
+        // An extra obstacle only matters when it's ahead and closer than the real stop.
+        let from = Point::new(2, 2);
+        let obstacle = |row, col| Point::new(row, col);
+        assert_eq!(
+            table.stop_with_obstacle(from, Direction::Right, obstacle(2, 4)),
+            Some(Point::new(2, 3))
+        );
+        assert_eq!(
+            table.stop_with_obstacle(from, Direction::Down, obstacle(4, 2)),
+            Some(Point::new(3, 2))
+        );
+        assert_eq!(
+            table.stop_with_obstacle(from, Direction::Up, obstacle(0, 2)),
+            Some(Point::new(1, 2))
+        );
+        assert_eq!(
+            table.stop_with_obstacle(from, Direction::Left, obstacle(2, 4)),
+            Some(Point::new(2, 1))
+        );
+        assert_eq!(
+            table.stop_with_obstacle(from, Direction::Right, obstacle(3, 4)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_matches_the_step_by_step_walk() {
+        for seed in 1..=20 {
+            let mut lab = build_lab_from_string(&random_map(seed * 7919, 24)).unwrap();
+            calculate_guard_path(&mut lab);
+            assert_eq!(
+                loop_obstacles(&lab).len(),
+                count_all_possible_guard_loops(&lab),
+                "seed {seed}"
+            );
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::{HashMap, HashSet};
+
+use commons::{
+    arg_parsing::ParsedArgs,
+    geometry::{Direction, Point},
+    solution::Solution,
+    AocError,
+};
+
+use crate::loop_finder::{loop_obstacles, split_across_threads};
+use crate::{invalid_format, step_guard, Day6, GuardStep, Lab};
+
+// The details behind the part two count: for every obstacle that traps the guard, when the loop
+// shows up and what it looks like. Steps count every move of the guard, turns included, as in
+// step_guard.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardLoop {
+    pub obstacle: Point,
+    // The move on which the guard is first back in a position and direction it's been in before.
+    pub detected_at_step: usize,
+    // How many moves one time round the loop takes.
+    pub period: usize,
+    // The cells on the loop, in the order the guard first reaches them.
+    pub cells: Vec<Point>,
+}
+
+// Walk the guard with `obstacle` added, one move at a time, remembering when each state was
+// first seen. None if the guard gets out.
+fn trace_loop(lab: &Lab, obstacle: Point) -> Option<GuardLoop> {
+    let mut lab = lab.clone();
+    lab.reset_to_starting_state();
+    lab.map[obstacle] = '#';
+    let mut first_seen: HashMap<(Point, Direction), usize> = HashMap::new();
+    let mut states = vec![(lab.guard_position, lab.guard_direction)];
+    first_seen.insert(states[0], 0);
+    loop {
+        if step_guard(&mut lab) == GuardStep::Exited {
+            return None;
+        }
+        let state = (lab.guard_position, lab.guard_direction);
+        let step = states.len();
+        if let Some(&loop_start) = first_seen.get(&state) {
+            let mut cells_seen = HashSet::new();
+            let cells = states[loop_start..]
+                .iter()
+                .map(|(position, _)| *position)
+                .filter(|position| cells_seen.insert(*position))
+                .collect();
+            return Some(GuardLoop {
+                obstacle,
+                detected_at_step: step,
+                period: step - loop_start,
+                cells,
+            });
+        }
+        first_seen.insert(state, step);
+        states.push(state);
+    }
+}
+
+// Every loop the guard can be trapped in with one extra obstacle, in row-major order of the
+// obstacles. `lab` has to have had its path walked already.
+pub fn guard_loops(lab: &Lab) -> Vec<GuardLoop> {
+    split_across_threads(&loop_obstacles(lab), |obstacles| {
+        obstacles
+            .iter()
+            .filter_map(|obstacle| trace_loop(lab, *obstacle))
+            .collect()
+    })
+}
+
+pub fn format_table(loops: &[GuardLoop]) -> String {
+    let mut table = format!(
+        "{:>5} {:>5} {:>9} {:>7} {:>6}\n",
+        "row", "col", "detected", "period", "cells"
+    );
+    for guard_loop in loops {
+        table.push_str(&format!(
+            "{:>5} {:>5} {:>9} {:>7} {:>6}\n",
+            guard_loop.obstacle.row,
+            guard_loop.obstacle.col,
+            guard_loop.detected_at_step,
+            guard_loop.period,
+            guard_loop.cells.len()
+        ));
+    }
+    table.push_str(&format!("{} loops\n", loops.len()));
+    table
+}
+
+// One object per loop, with positions as [row, col] pairs.
+pub fn format_json(loops: &[GuardLoop]) -> String {
+    let point = |point: &Point| format!("[{}, {}]", point.row, point.col);
+    let objects: Vec<String> = loops
+        .iter()
+        .map(|guard_loop| {
+            let cells: Vec<String> = guard_loop.cells.iter().map(point).collect();
+            format!(
+                "  {{\"obstacle\": {}, \"detected_at_step\": {}, \"period\": {}, \"cells\": [{}]}}",
+                point(&guard_loop.obstacle),
+                guard_loop.detected_at_step,
+                guard_loop.period,
+                cells.join(", ")
+            )
+        })
+        .collect();
+    if objects.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n{}\n]\n", objects.join(",\n"))
+    }
+}
+
+// The --loops mode, for both the day binary and the `aoc` runner. Prints the report as a table,
+// or as JSON with `--format json`.
+pub fn print_loops(parsed: &ParsedArgs, raw_input: &str) -> Result<bool, AocError> {
+    let lab = Day6.parse(raw_input)?;
+    let loops = guard_loops(&lab);
+    match parsed.value("format") {
+        Some("json") => print!("{}", format_json(&loops)),
+        Some("table") => print!("{}", format_table(&loops)),
+        other => return Err(invalid_format(other)),
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_lab_from_string, calculate_guard_path};
+
+    // The guard goes up, right, down and left around the obstacles, and then out to the left.
+    const SMALL_LAB: &str = ".#..\n...#\n.^..\n..#.\n";
+
+    #[test]
+    fn test_trace_loop() {
+        let mut lab = build_lab_from_string(SMALL_LAB).unwrap();
+        calculate_guard_path(&mut lab);
+        // An obstacle at (2, 0) turns it back up just where it started, closing a 2x2 loop.
+        let guard_loop = trace_loop(&lab, Point::new(2, 0)).unwrap();
+        assert_eq!(guard_loop.detected_at_step, 8);
+        assert_eq!(guard_loop.period, 8);
+        assert_eq!(
+            guard_loop.cells,
+            vec![
+                Point::new(2, 1),
+                Point::new(1, 1),
+                Point::new(1, 2),
+                Point::new(2, 2)
+            ]
+        );
+        assert_eq!(trace_loop(&lab, Point::new(0, 3)), None);
+    }
+
+    #[test]
+    fn test_example_loops() {
+        let mut lab = crate::build_test_lab();
+        calculate_guard_path(&mut lab);
+        let loops = guard_loops(&lab);
+        assert_eq!(
+            loops.iter().map(|l| l.obstacle).collect::<Vec<_>>(),
+            loop_obstacles(&lab)
+        );
+        assert_eq!(loops.len(), 6);
+        // The well known one: an obstacle right next to the guard's start.
+        let next_to_start = loops
+            .iter()
+            .find(|l| l.obstacle == Point::new(6, 3))
+            .unwrap();
+        assert!(next_to_start.cells.contains(&Point::new(6, 4)));
+        assert!(loops.iter().all(|l| l.period <= l.detected_at_step));
+    }
+
+    #[test]
+    fn test_formats() {
+        let loops = vec![GuardLoop {
+            obstacle: Point::new(6, 3),
+            detected_at_step: 20,
+            period: 12,
+            cells: vec![Point::new(6, 4), Point::new(5, 4)],
+        }];
+        assert_eq!(
+            format_table(&loops),
+            "  row   col  detected  period  cells\n    6     3        20      12      2\n1 loops\n"
+        );
+        assert_eq!(
+            format_json(&loops),
+            "[\n  {\"obstacle\": [6, 3], \"detected_at_step\": 20, \"period\": 12, \"cells\": [[6, 4], [5, 4]]}\n]\n"
+        );
+        assert_eq!(format_json(&[]), "[]\n");
+    }
+}
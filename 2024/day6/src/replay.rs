@@ -0,0 +1,236 @@
+use std::io::BufRead;
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use commons::AocError;
+
+use crate::{step_guard, GuardStep, Lab, VISITED_POS};
+
+// Watch the guard walk, one move per frame. Frames are either played in the terminal, with ANSI
+// colours and controls read from stdin, or written out as numbered text files.
+
+const RESET: &str = "\x1b[0m";
+const GUARD_COLOUR: &str = "\x1b[1;33m";
+const VISITED_COLOUR: &str = "\x1b[32m";
+const OBSTACLE_COLOUR: &str = "\x1b[31m";
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+const START_DELAY: Duration = Duration::from_millis(200);
+const MIN_DELAY: Duration = Duration::from_millis(5);
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+// The map with the guard drawn on it, one line per row, optionally coloured.
+pub fn render_map(lab: &Lab, colour: bool) -> String {
+    let mut frame = String::with_capacity(lab.map.len() * 2);
+    for (position, cell) in lab.map.iter() {
+        let (symbol, paint) = if position == lab.guard_position.into() {
+            // Unwrap: the guard only ever faces a cardinal direction.
+            (lab.guard_direction.arrow().unwrap(), GUARD_COLOUR)
+        } else if *cell == VISITED_POS {
+            (*cell, VISITED_COLOUR)
+        } else if *cell == '#' {
+            (*cell, OBSTACLE_COLOUR)
+        } else {
+            (*cell, "")
+        };
+        if colour && !paint.is_empty() {
+            frame.push_str(paint);
+            frame.push(symbol);
+            frame.push_str(RESET);
+        } else {
+            frame.push(symbol);
+        }
+        if position.1 + 1 == lab.map.width() {
+            frame.push('\n');
+        }
+    }
+    frame
+}
+
+// A line about where the guard is and how the walk is going.
+fn status_line(lab: &Lab, frame: usize, state: GuardStep) -> String {
+    let progress = match state {
+        GuardStep::Walking => "walking",
+        GuardStep::Exited => "left the lab",
+        GuardStep::Looped => "stuck in a loop",
+    };
+    format!(
+        "Frame {frame}: guard at {} facing {:?}, {} cells visited, {progress}",
+        lab.guard_position,
+        lab.guard_direction,
+        crate::count_visited_positions(lab)
+    )
+}
+
+// Write every frame of the walk to `dir`, as frame_00000.txt and so on, and return how many
+// there were. Frame 0 is the lab before the guard moves.
+pub fn write_frames(mut lab: Lab, dir: &Path) -> Result<usize, AocError> {
+    let io_error = |path: &Path| {
+        let path = path.display().to_string();
+        move |source| AocError::Io { path, source }
+    };
+    std::fs::create_dir_all(dir).map_err(io_error(dir))?;
+    let mut state = GuardStep::Walking;
+    let mut frame = 0;
+    loop {
+        let path = dir.join(format!("frame_{frame:05}.txt"));
+        let text = format!(
+            "{}\n{}",
+            status_line(&lab, frame, state),
+            render_map(&lab, false)
+        );
+        std::fs::write(&path, text).map_err(io_error(&path))?;
+        if state != GuardStep::Walking {
+            return Ok(frame + 1);
+        }
+        state = step_guard(&mut lab);
+        frame += 1;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Step,
+    PlayPause,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl Control {
+    // Controls are typed as a line, since stdin is line buffered. A bare Enter steps.
+    pub fn parse(line: &str) -> Option<Self> {
+        match line.trim() {
+            "" | "s" => Some(Control::Step),
+            "p" => Some(Control::PlayPause),
+            "+" | "f" => Some(Control::Faster),
+            "-" | "l" => Some(Control::Slower),
+            "q" => Some(Control::Quit),
+            _ => None,
+        }
+    }
+}
+
+const HELP: &str = "[Enter] step   p play/pause   + faster   - slower   q quit";
+
+// Play the walk in the terminal. It starts paused; each control is typed and followed by Enter.
+pub fn play(mut lab: Lab) {
+    // Stdin is read on its own thread, so that frames keep coming while nothing is typed.
+    let (sender, controls) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut state = GuardStep::Walking;
+    let mut frame = 0;
+    let mut playing = false;
+    let mut delay = START_DELAY;
+    let mut note = String::new();
+    loop {
+        print!(
+            "{CLEAR_SCREEN}{}\n{}\n{} at {}ms a frame. {HELP}\n{note}",
+            render_map(&lab, true),
+            status_line(&lab, frame, state),
+            if playing { "Playing" } else { "Paused" },
+            delay.as_millis()
+        );
+        note.clear();
+        if state != GuardStep::Walking {
+            println!();
+            return;
+        }
+
+        let line = if playing {
+            match controls.recv_timeout(delay) {
+                Ok(line) => Some(line),
+                Err(RecvTimeoutError::Timeout) => None,
+                // Nothing more will be typed, so just play to the end.
+                Err(RecvTimeoutError::Disconnected) => {
+                    std::thread::sleep(delay);
+                    None
+                }
+            }
+        } else {
+            match controls.recv() {
+                Ok(line) => Some(line),
+                Err(_) => return,
+            }
+        };
+        let advance = match line.as_deref().map(Control::parse) {
+            // A frame's worth of time went by while playing.
+            None => true,
+            Some(Some(Control::Step)) => !playing,
+            Some(Some(Control::PlayPause)) => {
+                playing = !playing;
+                false
+            }
+            Some(Some(Control::Faster)) => {
+                delay = (delay / 2).max(MIN_DELAY);
+                false
+            }
+            Some(Some(Control::Slower)) => {
+                delay = (delay * 2).min(MAX_DELAY);
+                false
+            }
+            Some(Some(Control::Quit)) => return,
+            Some(None) => {
+                note = format!("Unknown control '{}'\n", line.unwrap_or_default().trim());
+                false
+            }
+        };
+        if advance {
+            state = step_guard(&mut lab);
+            frame += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_lab_from_string;
+
+    #[test]
+    fn test_render_map() {
+        let mut lab = build_lab_from_string("..#\n.^.\n...\n").unwrap();
+        assert_eq!(render_map(&lab, false), "..#\n.^.\n...\n");
+        step_guard(&mut lab);
+        assert_eq!(render_map(&lab, false), ".^#\n.X.\n...\n");
+        let coloured = render_map(&lab, true);
+        assert!(coloured.starts_with(&format!(".{GUARD_COLOUR}^{RESET}{OBSTACLE_COLOUR}#")));
+        assert!(coloured.contains(&format!("{VISITED_COLOUR}X{RESET}")));
+    }
+
+    #[test]
+    fn test_controls() {
+        assert_eq!(Control::parse(""), Some(Control::Step));
+        assert_eq!(Control::parse(" p\n"), Some(Control::PlayPause));
+        assert_eq!(Control::parse("+"), Some(Control::Faster));
+        assert_eq!(Control::parse("-"), Some(Control::Slower));
+        assert_eq!(Control::parse("q"), Some(Control::Quit));
+        assert_eq!(Control::parse("x"), None);
+    }
+
+    #[test]
+    fn test_write_frames() {
+        let dir = std::env::temp_dir().join(format!("day6-frames-{}", std::process::id()));
+        let lab = build_lab_from_string("...\n.>.\n...\n").unwrap();
+        // Two steps to the edge, then one more to leave.
+        assert_eq!(write_frames(lab, &dir).unwrap(), 3);
+        let first = std::fs::read_to_string(dir.join("frame_00000.txt")).unwrap();
+        assert_eq!(
+            first,
+            "Frame 0: guard at (1, 1) facing Right, 0 cells visited, walking\n...\n.>.\n...\n"
+        );
+        let last = std::fs::read_to_string(dir.join("frame_00002.txt")).unwrap();
+        assert!(last.starts_with("Frame 2: guard at (1, 2) facing Right, 2 cells visited, left"));
+        assert!(last.ends_with("...\n.X>\n...\n"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
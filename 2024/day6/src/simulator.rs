@@ -0,0 +1,471 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use commons::{
+    geometry::{Direction, Point},
+    grid::Grid,
+    parse::{self, Span},
+    AocError,
+};
+
+// A more general lab than the puzzle's: any number of guards, a choice of what guards do when
+// they hit an obstacle, and optionally guards that crash into each other. The map format is the
+// puzzle's, with an optional block of "key: value" settings and a blank line before it:
+//
+//     turn: left
+//     collisions: on
+//
+//     ....#.....
+//     .^......<.
+//
+// Every arrow on the map is a guard. Guards are numbered from 1 in reading order, and take turns
+// to make one move each (a step forward, a turn, or leaving the lab). A guard is done when it
+// leaves the lab, when it's back in a position and direction it's been in before (a loop), or
+// when it collides. Done guards are taken out of the lab, apart from collided ones, which stay
+// where they crashed.
+//
+// That includes looped guards, even though a real one would carry on walking its loop forever.
+// So with collisions on, a guard whose path crosses a looped guard's loop after it was spotted
+// walks straight through, where it might really have crashed into it. A guard that runs into a
+// looped guard before the loop is spotted still crashes as usual.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnRule {
+    Right,
+    Left,
+    Reverse,
+}
+
+impl TurnRule {
+    pub fn turn(self, direction: Direction) -> Direction {
+        match self {
+            TurnRule::Right => direction.rotate_cw(),
+            TurnRule::Left => direction.rotate_ccw(),
+            TurnRule::Reverse => direction.opposite(),
+        }
+    }
+}
+
+impl Display for TurnRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TurnRule::Right => write!(f, "right"),
+            TurnRule::Left => write!(f, "left"),
+            TurnRule::Reverse => write!(f, "reverse"),
+        }
+    }
+}
+
+// The settings block. Without one, guards turn right and walk through each other, as in the
+// puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LabRules {
+    pub turn: TurnRule,
+    // With collisions, a guard that walks into another guard stops, and so does the other guard.
+    pub collisions: bool,
+}
+
+impl Default for LabRules {
+    fn default() -> Self {
+        LabRules {
+            turn: TurnRule::Right,
+            collisions: false,
+        }
+    }
+}
+
+// Where a guard starts, and which way it's facing.
+type GuardStart = (Point, Direction);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiGuardLab {
+    map: Grid<char>,
+    rules: LabRules,
+    // Where each guard starts, in reading order.
+    guards: Vec<GuardStart>,
+}
+
+fn parse_rules(lines: &[Span]) -> Result<LabRules, AocError> {
+    let mut rules = LabRules::default();
+    for line in lines {
+        let (key, value) = line.key_value(":")?;
+        match (key.text, value.text) {
+            ("turn", "right") => rules.turn = TurnRule::Right,
+            ("turn", "left") => rules.turn = TurnRule::Left,
+            ("turn", "reverse") => rules.turn = TurnRule::Reverse,
+            ("turn", _) => {
+                return Err(value.error(format!(
+                    "unknown turn rule '{}', expected right, left or reverse",
+                    value.text
+                )))
+            }
+            ("collisions", "on") => rules.collisions = true,
+            ("collisions", "off") => rules.collisions = false,
+            ("collisions", _) => {
+                return Err(value.error(format!(
+                    "expected collisions to be on or off, found '{}'",
+                    value.text
+                )))
+            }
+            _ => {
+                return Err(key.error(format!(
+                    "unknown setting '{}', expected turn or collisions",
+                    key.text
+                )))
+            }
+        }
+    }
+    Ok(rules)
+}
+
+fn parse_map(lines: &[Span]) -> Result<(Grid<char>, Vec<GuardStart>), AocError> {
+    let mut rows = Vec::with_capacity(lines.len());
+    let mut guards = Vec::new();
+    for line in lines {
+        let mut row = Vec::with_capacity(line.text.len());
+        for (col, c) in line.text.chars().enumerate() {
+            if let Some(direction) = Direction::from_arrow(c) {
+                guards.push((Point::new(rows.len(), col), direction));
+                row.push('.');
+            } else if c == '.' || c == '#' {
+                row.push(c);
+            } else {
+                return Err(AocError::parse_at(
+                    line.line,
+                    line.column + col,
+                    format!("unexpected character '{c}' in the map"),
+                ));
+            }
+        }
+        if row.len() != lines[0].text.chars().count() {
+            return Err(line.error(format!(
+                "expected a row of {} cells, found {}",
+                lines[0].text.chars().count(),
+                row.len()
+            )));
+        }
+        rows.push(row);
+    }
+    if guards.is_empty() {
+        return Err(AocError::validation("the lab map has no guards in it"));
+    }
+    Ok((Grid::from_rows(rows)?, guards))
+}
+
+impl MultiGuardLab {
+    pub fn parse(text: &str) -> Result<Self, AocError> {
+        let sections: Vec<_> = parse::sections(text).collect();
+        let (settings, map_lines) = match sections.as_slice() {
+            [map] => (&[][..], map),
+            [settings, map] => (&settings[..], map),
+            [] => return Err(AocError::validation("the lab map is empty")),
+            [_, _, extra, ..] => {
+                return Err(extra[0].error("expected some settings, a blank line and then the map"))
+            }
+        };
+        let rules = parse_rules(settings)?;
+        let (map, guards) = parse_map(map_lines)?;
+        Ok(MultiGuardLab { map, rules, guards })
+    }
+
+    pub fn rules(&self) -> LabRules {
+        self.rules
+    }
+
+    pub fn guard_count(&self) -> usize {
+        self.guards.len()
+    }
+
+    pub fn simulate(&self) -> Vec<GuardReport> {
+        let mut walkers: Vec<Walker> = self
+            .guards
+            .iter()
+            .map(|&(start, direction)| Walker::new(start, direction))
+            .collect();
+        let mut step = 0;
+        while walkers.iter().any(|walker| walker.outcome.is_none()) {
+            step += 1;
+            for id in 0..walkers.len() {
+                if walkers[id].outcome.is_none() {
+                    self.move_guard(&mut walkers, id, step);
+                }
+            }
+        }
+        walkers
+            .into_iter()
+            .zip(self.guards.iter())
+            .enumerate()
+            .map(|(idx, (walker, &(start, direction)))| GuardReport {
+                guard: idx + 1,
+                start,
+                direction,
+                end: walker.position,
+                path: walker.path,
+                // Unwrap: the simulation only stops once every guard is done.
+                outcome: walker.outcome.unwrap(),
+            })
+            .collect()
+    }
+
+    fn move_guard(&self, walkers: &mut [Walker], id: usize, step: usize) {
+        let Walker {
+            position,
+            direction,
+            ..
+        } = walkers[id];
+        match self.map.step(position, direction) {
+            None => {
+                walkers[id].outcome = Some(Outcome::Exited { step });
+                return;
+            }
+            Some(ahead) if self.map[ahead] == '#' => {
+                walkers[id].direction = self.rules.turn.turn(direction);
+            }
+            Some(ahead) => {
+                let in_the_way = walkers.iter().position(|other| {
+                    other.position == ahead
+                        && matches!(other.outcome, None | Some(Outcome::Collided { .. }))
+                });
+                match in_the_way {
+                    Some(other) if self.rules.collisions && other != id => {
+                        walkers[id].outcome = Some(Outcome::Collided {
+                            step,
+                            with: other + 1,
+                        });
+                        if walkers[other].outcome.is_none() {
+                            walkers[other].outcome = Some(Outcome::Collided { step, with: id + 1 });
+                        }
+                        return;
+                    }
+                    _ => walkers[id].advance_to(ahead),
+                }
+            }
+        }
+        let walker = &mut walkers[id];
+        let state = (walker.position, walker.direction);
+        match walker.seen.get(&state) {
+            Some(first) => {
+                walker.outcome = Some(Outcome::Looped {
+                    step,
+                    period: step - first,
+                })
+            }
+            None => {
+                walker.seen.insert(state, step);
+            }
+        }
+    }
+}
+
+struct Walker {
+    position: Point,
+    direction: Direction,
+    path: Vec<Point>,
+    visited: HashSet<Point>,
+    // The step on which each (position, direction) was first reached, to spot loops.
+    seen: HashMap<(Point, Direction), usize>,
+    outcome: Option<Outcome>,
+}
+
+impl Walker {
+    fn new(start: Point, direction: Direction) -> Self {
+        Walker {
+            position: start,
+            direction,
+            path: vec![start],
+            visited: HashSet::from([start]),
+            seen: HashMap::from([((start, direction), 0)]),
+            outcome: None,
+        }
+    }
+
+    fn advance_to(&mut self, position: Point) {
+        self.position = position;
+        if self.visited.insert(position) {
+            self.path.push(position);
+        }
+    }
+}
+
+// How a guard's walk ended. Steps count every move, turns included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Exited { step: usize },
+    // `period` is how many moves one time round the loop takes.
+    Looped { step: usize, period: usize },
+    Collided { step: usize, with: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardReport {
+    pub guard: usize,
+    pub start: Point,
+    pub direction: Direction,
+    // Where the guard was when it was done: the cell it left from, crashed on or spotted its
+    // loop on. Not always the last cell of `path`, if it walked back over cells it had been on.
+    pub end: Point,
+    // Every cell the guard stood on, in the order it first got there.
+    pub path: Vec<Point>,
+    pub outcome: Outcome,
+}
+
+impl Display for GuardReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Guard {} from {} facing {:?}: ",
+            self.guard, self.start, self.direction
+        )?;
+        match self.outcome {
+            Outcome::Exited { step } => write!(f, "left the lab from {} on move {step}", self.end)?,
+            Outcome::Looped { step, period } => write!(
+                f,
+                "stuck in a loop of {period} moves, spotted on move {step}"
+            )?,
+            Outcome::Collided { step, with } => {
+                write!(f, "collided with guard {with} on move {step}")?
+            }
+        }
+        write!(f, ", {} cells visited", self.path.len())
+    }
+}
+
+pub fn format_report(lab: &MultiGuardLab, reports: &[GuardReport]) -> String {
+    let rules = lab.rules();
+    let mut text = format!(
+        "{} guards, turning {}, collisions {}\n",
+        lab.guard_count(),
+        rules.turn,
+        if rules.collisions { "on" } else { "off" }
+    );
+    for report in reports {
+        text.push_str(&format!("{report}\n"));
+    }
+    text
+}
+
+// One object per guard, with positions as [row, col] pairs, like loop_report::format_json.
+pub fn format_json(reports: &[GuardReport]) -> String {
+    let point = |point: &Point| format!("[{}, {}]", point.row, point.col);
+    let objects: Vec<String> = reports
+        .iter()
+        .map(|report| {
+            let outcome = match report.outcome {
+                Outcome::Exited { step } => format!("{{\"kind\": \"exited\", \"step\": {step}}}"),
+                Outcome::Looped { step, period } => format!(
+                    "{{\"kind\": \"looped\", \"step\": {step}, \"period\": {period}}}"
+                ),
+                Outcome::Collided { step, with } => format!(
+                    "{{\"kind\": \"collided\", \"step\": {step}, \"with\": {with}}}"
+                ),
+            };
+            let path: Vec<String> = report.path.iter().map(point).collect();
+            format!(
+                "  {{\"guard\": {}, \"start\": {}, \"direction\": \"{:?}\", \"end\": {}, \"outcome\": {outcome}, \"path\": [{}]}}",
+                report.guard,
+                point(&report.start),
+                report.direction,
+                point(&report.end),
+                path.join(", ")
+            )
+        })
+        .collect();
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_the_puzzle_rules() {
+        let example = crate::read_example_input();
+        let lab = MultiGuardLab::parse(&example).unwrap();
+        assert_eq!(lab.rules(), LabRules::default());
+        let reports = lab.simulate();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].path.len(), 41);
+        assert_eq!(reports[0].outcome, Outcome::Exited { step: 55 });
+        assert_eq!(reports[0].start, Point::new(6, 4));
+    }
+
+    #[test]
+    fn test_settings_and_turn_rules() {
+        // Turning left at the obstacle sends the guard off the left edge.
+        let lab = MultiGuardLab::parse("turn: left\n\n.#.\n...\n.^.\n").unwrap();
+        let reports = lab.simulate();
+        assert_eq!(reports[0].outcome, Outcome::Exited { step: 4 });
+        assert_eq!(reports[0].end, Point::new(1, 0));
+        // Reversing bounces the guard between the two obstacles forever.
+        let lab = MultiGuardLab::parse("turn: reverse\n\n#\n.\n^\n.\n#\n").unwrap();
+        let reports = lab.simulate();
+        assert_eq!(reports[0].outcome, Outcome::Looped { step: 6, period: 6 });
+        assert_eq!(reports[0].path.len(), 3);
+        // Turning back, the guard leaves through the cells it came in on.
+        let lab = MultiGuardLab::parse("turn: reverse\n\n#\n.\n^\n").unwrap();
+        let reports = lab.simulate();
+        assert_eq!(reports[0].outcome, Outcome::Exited { step: 4 });
+        assert_eq!(reports[0].end, Point::new(2, 0));
+        assert_eq!(reports[0].path.last(), Some(&Point::new(1, 0)));
+        assert_eq!(
+            reports[0].to_string(),
+            "Guard 1 from (2, 0) facing Up: left the lab from (2, 0) on move 4, 2 cells visited"
+        );
+
+        let error_at = |text: &str| match MultiGuardLab::parse(text) {
+            Err(AocError::Parse { line, column, .. }) => (line, column),
+            other => panic!("Expected a parse error, got {other:?}"),
+        };
+        assert_eq!(error_at("turn: around\n\n^\n"), (1, Some(7)));
+        assert_eq!(error_at("speed: 2\n\n^\n"), (1, Some(1)));
+        assert_eq!(error_at("turn left\n\n^\n"), (1, Some(1)));
+        assert_eq!(error_at("..\n.^x\n"), (2, Some(3)));
+        assert_eq!(error_at("..\n.^.\n"), (2, Some(1)));
+        assert!(matches!(
+            MultiGuardLab::parse("...\n"),
+            Err(AocError::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_several_guards() {
+        // Two guards walking towards each other along the same row.
+        let map = "#......#\n.>....<.\n";
+        let lab = MultiGuardLab::parse(map).unwrap();
+        assert_eq!(lab.guard_count(), 2);
+        let reports = lab.simulate();
+        // Without collisions they pass straight through each other and walk off the edges.
+        assert!(reports
+            .iter()
+            .all(|report| matches!(report.outcome, Outcome::Exited { .. })));
+
+        let lab = MultiGuardLab::parse(&format!("collisions: on\n\n{map}")).unwrap();
+        let reports = lab.simulate();
+        // Guard 1 moves first, so it's the one that walks into guard 2 on the third move.
+        assert_eq!(reports[0].outcome, Outcome::Collided { step: 3, with: 2 });
+        assert_eq!(reports[1].outcome, Outcome::Collided { step: 3, with: 1 });
+        assert_eq!(reports[0].end, Point::new(1, 3));
+        assert_eq!(reports[1].end, Point::new(1, 4));
+        assert_eq!(
+            reports[0].to_string(),
+            "Guard 1 from (1, 1) facing Right: collided with guard 2 on move 3, 3 cells visited"
+        );
+        assert!(format_json(&reports).contains(
+            "\"outcome\": {\"kind\": \"collided\", \"step\": 3, \"with\": 1}, \"path\": [[1, 6], [1, 5], [1, 4]]"
+        ));
+        assert!(
+            format_report(&lab, &reports).starts_with("2 guards, turning right, collisions on\n")
+        );
+
+        // Guard 1 bounces up and down the first column, and is spotted looping on move 6. Guard 2
+        // only gets there on move 7, by which time guard 1 is out of the lab.
+        let lab = MultiGuardLab::parse(
+            "turn: reverse\ncollisions: on\n\n#......\n.......\n^.....<\n.......\n#......\n",
+        )
+        .unwrap();
+        let reports = lab.simulate();
+        assert_eq!(reports[0].outcome, Outcome::Looped { step: 6, period: 6 });
+        assert!(matches!(reports[1].outcome, Outcome::Exited { .. }));
+        assert!(reports[1].path.contains(&Point::new(2, 0)));
+    }
+}
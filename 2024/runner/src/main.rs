@@ -0,0 +1,77 @@
+use commons::{
+    arg_parsing::{ArgParser, Flag},
+    runner::{run_from_env_args, runner_arg_parser, Registry},
+};
+
+// Every solution that the `aoc` binary can dispatch to, and the modes it offers on top of solving
+// them. New days only need a line here.
+fn build_registry() -> Registry {
+    let mut registry = Registry::new();
+    registry.register(2024, 1, day1::Day1);
+    registry.register(2024, 2, day2::Day2);
+    registry.register(2024, 3, day3::Day3);
+    registry.register(2024, 4, day4::Day4);
+    registry.register(2024, 5, day5::Day5);
+    registry.register(2024, 6, day6::Day6);
+    registry.register_mode(
+        2024,
+        6,
+        "loops",
+        "For 2024 day 6, list every obstacle that traps the guard, with when and how it loops",
+        day6::loop_report::print_loops,
+    );
+    registry.register(2023, 1, aoc2023_day1::Day1);
+    registry.register(2023, 2, aoc2023_day2::Day2);
+    registry.register(2023, 3, aoc2023_day3::Day3);
+    registry.register(2023, 4, aoc2023_day4::Day4);
+    registry.register(2023, 5, aoc2023_day5::Day5);
+    registry.register(2023, 6, aoc2023_day6::Day6);
+    registry.register(2023, 7, aoc2023_day7::Day7);
+    registry.register(2023, 8, aoc2023_day8::Day8);
+    registry
+}
+
+// The day binaries share the runner's flags, but only `aoc` checks the recorded answers. The
+// registered modes each get a switch, and any options they take are added here.
+fn arg_parser(registry: &Registry) -> ArgParser {
+    let parser = runner_arg_parser()
+        .flag(Flag::switch(
+            "check",
+            "Run every input with a recorded answer and compare against answers.txt",
+        ))
+        .flag(
+            Flag::value("format", "FORMAT", "With --loops, print a table or json")
+                .default_value("table"),
+        );
+    registry.mode_switches(parser)
+}
+
+fn main() {
+    let registry = build_registry();
+    run_from_env_args(&arg_parser(&registry), &registry);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commons::runner::{check, runner_args_from};
+
+    #[test]
+    fn test_recorded_answers_still_match() {
+        let registry = build_registry();
+        let parsed = arg_parser(&registry)
+            .parse(&["--check".to_string()])
+            .unwrap();
+        let args = runner_args_from(&parsed, None).unwrap();
+        assert!(check(&registry, &args).unwrap());
+        let parsed = arg_parser(&registry)
+            .parse(&[
+                "--check".to_string(),
+                "--year".to_string(),
+                "2023".to_string(),
+            ])
+            .unwrap();
+        let args = runner_args_from(&parsed, None).unwrap();
+        assert!(check(&registry, &args).unwrap());
+    }
+}